@@ -0,0 +1,395 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::har;
+
+/// Statuses a cache may store without explicit freshness information
+/// (RFC 9110 §15.1), given a `Last-Modified` to estimate a lifetime from.
+const HEURISTIC_STATUSES: &[i64] = &[200, 203, 204, 206, 300, 301, 308, 404, 405, 410, 414, 501];
+
+/// How long a response stays fresh and what that is based on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lifetime {
+    pub seconds: u64,
+    /// e.g. `max-age`, `Expires` or `heuristic`.
+    pub source: &'static str,
+}
+
+/// Whether the request was conditional, and how the server answered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Conditional {
+    No,
+    /// 304: the cached body was reused.
+    NotModified,
+    /// The full body was sent again.
+    Modified,
+}
+
+/// Cacheability of one response, from a browser cache's point of view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Analysis {
+    /// Whether the browser may store and reuse the response.
+    pub cacheable: bool,
+    /// Why it is not cacheable, or what limits its reuse.
+    pub notes: Vec<String>,
+    pub lifetime: Option<Lifetime>,
+    /// `Age` header: seconds the response already spent in shared caches.
+    pub age: u64,
+    /// `ETag` and `Last-Modified`, the headers a conditional request can use.
+    pub validators: Vec<(&'static str, String)>,
+    pub conditional: Conditional,
+    /// `memory`, `disk` or `cache` when the browser did not hit the network.
+    pub from_cache: Option<String>,
+}
+
+impl Analysis {
+    /// Freshness left when the response was received, in seconds.
+    pub fn remaining(&self) -> u64 {
+        self.lifetime.as_ref().map_or(0, |l| l.seconds.saturating_sub(self.age))
+    }
+}
+
+/// `Cache-Control` directives as lowercase name and unquoted value.
+pub fn directives(value: &str) -> Vec<(String, Option<String>)> {
+    value
+        .split(',')
+        .filter_map(|d| {
+            let (name, value) = match d.split_once('=') {
+                Some((name, value)) => (name, Some(value.trim().trim_matches('"').to_string())),
+                None => (d, None),
+            };
+            let name = name.trim().to_lowercase();
+            (!name.is_empty()).then_some((name, value))
+        })
+        .collect()
+}
+
+fn http_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value.trim()).ok().map(|t| t.with_timezone(&Utc))
+}
+
+fn started(entry: &har::Entry) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(&entry.started_date_time)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+fn seconds_between(from: DateTime<Utc>, to: DateTime<Utc>) -> u64 {
+    (to - from).num_seconds().max(0) as u64
+}
+
+/// Analyse the caching headers of `entry`'s response.
+pub fn analyze(entry: &har::Entry) -> Analysis {
+    let request = &entry.request;
+    let response = &entry.response;
+    let cache_control = directives(response.header("cache-control").unwrap_or_default());
+    let directive = |name: &str| cache_control.iter().find(|(n, _)| n == name);
+    let mut notes = Vec::new();
+
+    let validators: Vec<(&'static str, String)> = [("ETag", "etag"), ("Last-Modified", "last-modified")]
+        .into_iter()
+        .filter_map(|(label, name)| Some((label, response.header(name)?.to_string())))
+        .collect();
+    let date = response.header("date").and_then(http_date).or_else(|| started(entry));
+
+    let lifetime = if directive("no-cache").is_some() {
+        notes.push("no-cache: revalidated with the server before every use".to_string());
+        Some(Lifetime {
+            seconds: 0,
+            source: "no-cache",
+        })
+    } else if let Some((_, value)) = directive("max-age") {
+        Some(Lifetime {
+            seconds: value.as_deref().and_then(|v| v.parse().ok()).unwrap_or(0),
+            source: "max-age",
+        })
+    } else if let Some(expires) = response.header("expires") {
+        let seconds = match (http_date(expires), date) {
+            (Some(expires), Some(date)) => seconds_between(date, expires),
+            // An invalid date, typically `0` or `-1`, means already expired.
+            _ => 0,
+        };
+        Some(Lifetime {
+            seconds,
+            source: "Expires",
+        })
+    } else {
+        let last_modified = response.header("last-modified").and_then(http_date);
+        match (last_modified, date) {
+            (Some(modified), Some(date)) if HEURISTIC_STATUSES.contains(&response.status) => Some(Lifetime {
+                seconds: seconds_between(modified, date) / 10,
+                source: "heuristic, 10% of the time since Last-Modified",
+            }),
+            _ => None,
+        }
+    };
+
+    let method = request.method.to_uppercase();
+    let not_cacheable = if !matches!(method.as_str(), "GET" | "HEAD") {
+        Some(format!("{} responses are not reused from the cache", method))
+    } else if response.status == 0 {
+        Some("no response was received".to_string())
+    } else if directive("no-store").is_some() {
+        Some("Cache-Control: no-store".to_string())
+    } else if response.header("vary").is_some_and(|v| v.trim() == "*") {
+        Some("Vary: * never matches a later request".to_string())
+    } else if lifetime.is_none() && validators.is_empty() {
+        Some("no freshness lifetime or validators".to_string())
+    } else {
+        None
+    };
+    let cacheable = not_cacheable.is_none();
+    if let Some(reason) = not_cacheable {
+        notes.insert(0, reason);
+    } else if lifetime.is_none() {
+        notes.push("no freshness lifetime: reused only after revalidation".to_string());
+    }
+
+    let conditional = match request.header("if-none-match").or(request.header("if-modified-since")) {
+        None => Conditional::No,
+        Some(_) if response.status == 304 => Conditional::NotModified,
+        Some(_) => Conditional::Modified,
+    };
+    let hit = entry
+        .cache
+        .before_request
+        .as_ref()
+        .is_some_and(|c| c.hit_count > 0)
+        .then(|| "cache".to_string());
+
+    Analysis {
+        cacheable,
+        notes,
+        lifetime,
+        age: response
+            .header("age")
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(0),
+        validators,
+        conditional,
+        from_cache: entry.from_cache.clone().filter(|s| !s.is_empty()).or(hit),
+    }
+}
+
+/// An asset fetched in full more than once with the same content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repeat {
+    pub url: String,
+    /// Indices into `har.log.entries` of every full download.
+    pub entries: Vec<usize>,
+    /// Body bytes of every download after the first.
+    pub wasted_bytes: u64,
+    /// Why the cache did not serve the later downloads.
+    pub reason: String,
+}
+
+/// Response body bytes on the wire, or the decoded size when that is unknown.
+pub fn transferred(entry: &har::Entry) -> u64 {
+    let response = &entry.response;
+    response
+        .body_size
+        .filter(|&size| size > 0)
+        .or(response.content.size)
+        .unwrap_or(0)
+        .max(0) as u64
+}
+
+fn same_content(a: &har::Entry, b: &har::Entry) -> bool {
+    let (a, b) = (&a.response.content, &b.response.content);
+    a.size == b.size && (a.text.is_none() || b.text.is_none() || a.text == b.text)
+}
+
+/// Assets downloaded in full more than once although their content did not
+/// change, most wasted bytes first.
+pub fn repeated_downloads(entries: &[har::Entry]) -> Vec<Repeat> {
+    let mut order: Vec<String> = Vec::new();
+    let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let full = entry.request.method.eq_ignore_ascii_case("GET")
+            && entry.response.status == 200
+            && entry.from_cache.as_deref().unwrap_or_default().is_empty();
+        if !full {
+            continue;
+        }
+        let mut url = entry.request.url.clone();
+        url.set_fragment(None);
+        let url = url.to_string();
+        if !groups.contains_key(&url) {
+            order.push(url.clone());
+        }
+        groups.entry(url).or_default().push(index);
+    }
+
+    let mut repeats: Vec<Repeat> = order
+        .into_iter()
+        .filter_map(|url| {
+            let downloads = groups.remove(&url)?;
+            let first = &entries[downloads[0]];
+            let second = &entries[*downloads.get(1)?];
+            if !downloads.iter().all(|&i| same_content(first, &entries[i])) {
+                return None;
+            }
+            Some(Repeat {
+                wasted_bytes: downloads[1..].iter().map(|&i| transferred(&entries[i])).sum(),
+                reason: repeat_reason(first, second),
+                url,
+                entries: downloads,
+            })
+        })
+        .collect();
+    repeats.sort_by_key(|r| std::cmp::Reverse(r.wasted_bytes));
+    repeats
+}
+
+/// Why `second` was downloaded again instead of reusing `first`.
+fn repeat_reason(first: &har::Entry, second: &har::Entry) -> String {
+    let analysis = analyze(first);
+    if !analysis.cacheable {
+        return format!("not cacheable: {}", analysis.notes[0]);
+    }
+    let gap = match (started(first), started(second)) {
+        (Some(a), Some(b)) => seconds_between(a, b),
+        _ => 0,
+    };
+    if analysis.remaining() > gap {
+        "downloaded again while still fresh (cache disabled?)".to_string()
+    } else if !analysis.validators.is_empty() {
+        "stale but unchanged: a conditional request would have got a 304".to_string()
+    } else {
+        format!(
+            "freshness lifetime of {}s ran out before it was needed again",
+            analysis.remaining()
+        )
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(url: &str, started: &str, request: &[(&str, &str)], status: i64, response: &[(&str, &str)]) -> har::Entry {
+        let mut entry = har::Entry::test("GET", url, status)
+            .with_started(started)
+            .with_time(20.0)
+            .with_request_headers(request)
+            .with_response_headers(response)
+            .with_response_body("application/javascript", "x");
+        entry.response.content.size = Some(1000);
+        entry.response.body_size = Some(400);
+        entry
+    }
+
+    const NOON: &str = "2024-06-01T12:00:00.000Z";
+    const DATE: &str = "Sat, 01 Jun 2024 12:00:00 GMT";
+
+    #[test]
+    fn freshness_comes_from_max_age_expires_or_heuristics() {
+        let max_age = analyze(&entry(
+            "https://x.test/a.js",
+            NOON,
+            &[],
+            200,
+            &[("Cache-Control", "public, max-age=3600"), ("Expires", "0"), ("Age", "600")],
+        ));
+        assert!(max_age.cacheable);
+        assert_eq!(max_age.lifetime.as_ref().unwrap().source, "max-age");
+        assert_eq!(max_age.remaining(), 3000);
+
+        let expires = analyze(&entry(
+            "https://x.test/a.js",
+            NOON,
+            &[],
+            200,
+            &[("Date", DATE), ("Expires", "Sat, 01 Jun 2024 13:00:00 GMT")],
+        ));
+        assert_eq!(expires.lifetime.unwrap().seconds, 3600);
+
+        let heuristic = analyze(&entry(
+            "https://x.test/a.js",
+            NOON,
+            &[],
+            200,
+            &[("Date", DATE), ("Last-Modified", "Tue, 21 May 2024 12:00:00 GMT")],
+        ));
+        assert_eq!(heuristic.lifetime.unwrap().seconds, 95_040);
+        assert_eq!(heuristic.validators[0].0, "Last-Modified");
+    }
+
+    #[test]
+    fn explains_what_is_not_cacheable() {
+        let no_store = analyze(&entry(
+            "https://x.test/",
+            NOON,
+            &[],
+            200,
+            &[("Cache-Control", "no-store"), ("ETag", "\"v1\"")],
+        ));
+        assert!(!no_store.cacheable);
+        assert_eq!(no_store.notes, vec!["Cache-Control: no-store"]);
+
+        let bare = analyze(&entry("https://x.test/", NOON, &[], 200, &[]));
+        assert!(!bare.cacheable);
+        assert_eq!(bare.notes, vec!["no freshness lifetime or validators"]);
+
+        let no_cache = analyze(&entry(
+            "https://x.test/",
+            NOON,
+            &[("If-None-Match", "\"v1\"")],
+            304,
+            &[("Cache-Control", "no-cache"), ("ETag", "\"v1\"")],
+        ));
+        assert!(no_cache.cacheable);
+        assert_eq!(no_cache.remaining(), 0);
+        assert_eq!(no_cache.conditional, Conditional::NotModified);
+    }
+
+    #[test]
+    fn reports_assets_downloaded_again_unchanged() {
+        let fresh = [("Cache-Control", "max-age=86400")];
+        let tagged = [("Cache-Control", "max-age=60"), ("ETag", "\"v1\"")];
+        let mut changed = entry("https://x.test/api", "2024-06-01T12:00:05.000Z", &[], 200, &[]);
+        changed.response.content.text = Some("y".to_string());
+        let mut memory = entry("https://x.test/app.js", "2024-06-01T12:00:09.000Z", &[], 200, &fresh);
+        memory.from_cache = Some("memory".to_string());
+        let entries = vec![
+            entry("https://x.test/app.js", NOON, &[], 200, &fresh),
+            entry("https://x.test/logo.png", NOON, &[], 200, &tagged),
+            entry("https://x.test/api", NOON, &[], 200, &[]),
+            entry("https://x.test/app.js#v", "2024-06-01T12:00:10.000Z", &[], 200, &fresh),
+            entry("https://x.test/app.js", "2024-06-01T12:00:20.000Z", &[], 200, &fresh),
+            entry("https://x.test/logo.png", "2024-06-01T12:05:00.000Z", &[], 200, &tagged),
+            changed,
+            memory,
+        ];
+        let repeats = repeated_downloads(&entries);
+        let summary: Vec<_> = repeats
+            .iter()
+            .map(|r| (r.url.as_str(), r.entries.clone(), r.wasted_bytes))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("https://x.test/app.js", vec![0, 3, 4], 800),
+                ("https://x.test/logo.png", vec![1, 5], 400),
+            ]
+        );
+        assert!(repeats[0].reason.starts_with("downloaded again while still fresh"));
+        assert!(repeats[1].reason.contains("304"));
+    }
+
+    #[test]
+    fn reads_the_har_cache_model() {
+        let mut hit = entry("https://x.test/a.js", NOON, &[], 200, &[("Cache-Control", "max-age=60")]);
+        hit.cache.before_request = Some(har::CacheEntry {
+            hit_count: 2,
+            ..Default::default()
+        });
+        assert_eq!(analyze(&hit).from_cache.as_deref(), Some("cache"));
+        let json = serde_json::to_value(&hit.cache).unwrap();
+        assert_eq!(json["beforeRequest"]["hitCount"], 2);
+        assert_eq!(json["beforeRequest"]["eTag"], "");
+    }
+}
@@ -0,0 +1,125 @@
+use encoding_rs::{Encoding, UTF_8};
+use std::sync::LazyLock;
+
+/// How far into an HTML/XML document to look for an in-band charset declaration.
+const SNIFF_LEN: usize = 1024;
+
+static META_CHARSET: LazyLock<regex::bytes::Regex> = LazyLock::new(|| {
+    regex::bytes::Regex::new(r#"(?i)<meta[^>]*?charset\s*=\s*["']?\s*([a-z0-9_\-:.]+)"#).unwrap()
+});
+
+static XML_ENCODING: LazyLock<regex::bytes::Regex> = LazyLock::new(|| {
+    regex::bytes::Regex::new(r#"(?i)^\s*<\?xml[^>]*?encoding\s*=\s*["']([a-z0-9_\-:.]+)["']"#).unwrap()
+});
+
+/// `charset` parameter of a MIME type such as `text/html; charset=Shift_JIS`.
+pub fn charset_param(mime_type: &str) -> Option<&str> {
+    mime_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("charset")
+            .then(|| value.trim().trim_matches('"'))
+    })
+}
+
+/// Charset declared for a body, in order of precedence: byte-order mark,
+/// `charset` of the content MIME type, `charset` of the `Content-Type` header,
+/// then a `<meta charset>` or XML declaration inside the document.
+pub fn declared(
+    mime_type: Option<&str>,
+    content_type: Option<&str>,
+    bytes: &[u8],
+) -> Option<&'static Encoding> {
+    if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+        return Some(encoding);
+    }
+    let from_label = |mime: Option<&str>| {
+        mime.and_then(charset_param)
+            .and_then(|label| Encoding::for_label(label.as_bytes()))
+    };
+    from_label(mime_type)
+        .or_else(|| from_label(content_type))
+        .or_else(|| sniff_document(bytes))
+}
+
+/// Encoding to decode a body with; UTF-8 unless something says otherwise.
+pub fn detect(mime_type: Option<&str>, content_type: Option<&str>, bytes: &[u8]) -> &'static Encoding {
+    declared(mime_type, content_type, bytes).unwrap_or(UTF_8)
+}
+
+fn sniff_document(bytes: &[u8]) -> Option<&'static Encoding> {
+    let head = &bytes[..bytes.len().min(SNIFF_LEN)];
+    let label = META_CHARSET
+        .captures(head)
+        .or_else(|| XML_ENCODING.captures(head))?
+        .get(1)?;
+    Encoding::for_label(label.as_bytes())
+}
+
+/// Decode `bytes` to a string, replacing malformed sequences and dropping a BOM.
+pub fn decode(bytes: &[u8], encoding: &'static Encoding) -> String {
+    let (text, _, _) = encoding.decode(bytes);
+    text.into_owned()
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn charset_param_is_parsed() {
+        assert_eq!(charset_param("text/html; charset=Shift_JIS"), Some("Shift_JIS"));
+        assert_eq!(charset_param("text/plain;CHARSET=\"euc-kr\""), Some("euc-kr"));
+        assert_eq!(charset_param("application/json"), None);
+    }
+
+    #[test]
+    fn mime_type_charset_wins_over_header() {
+        let enc = detect(Some("text/plain; charset=iso-8859-2"), Some("text/plain; charset=utf-8"), b"x");
+        assert_eq!(enc.name(), "ISO-8859-2");
+    }
+
+    #[test]
+    fn header_charset_used_when_mime_has_none() {
+        let enc = detect(Some("text/plain"), Some("text/plain; charset=windows-1251"), b"x");
+        assert_eq!(enc.name(), "windows-1251");
+    }
+
+    #[test]
+    fn bom_wins_over_labels() {
+        let enc = detect(Some("text/plain; charset=iso-8859-1"), None, &[0xff, 0xfe, b'h', 0]);
+        assert_eq!(enc.name(), "UTF-16LE");
+    }
+
+    #[test]
+    fn html_meta_and_xml_declaration_are_sniffed() {
+        let html = br#"<html><head><meta charset="euc-kr"></head></html>"#;
+        assert_eq!(detect(Some("text/html"), None, html).name(), "EUC-KR");
+
+        let http_equiv =
+            br#"<meta http-equiv="Content-Type" content="text/html; charset=Shift_JIS">"#;
+        assert_eq!(detect(None, None, http_equiv).name(), "Shift_JIS");
+
+        let xml = br#"<?xml version="1.0" encoding="ISO-8859-15"?><a/>"#;
+        assert_eq!(detect(None, None, xml).name(), "ISO-8859-15");
+    }
+
+    #[test]
+    fn defaults_to_utf8() {
+        assert_eq!(detect(None, None, b"plain").name(), "UTF-8");
+        assert!(declared(Some("text/plain; charset=bogus"), None, b"plain").is_none());
+    }
+
+    #[test]
+    fn decodes_legacy_encodings() {
+        // "日本" in Shift_JIS
+        let sjis = [0x93, 0xfa, 0x96, 0x7b];
+        assert_eq!(decode(&sjis, encoding_rs::SHIFT_JIS), "日本");
+        // "café" in windows-1252
+        assert_eq!(decode(b"caf\xe9", encoding_rs::WINDOWS_1252), "café");
+        // "hi" in UTF-16LE with BOM
+        assert_eq!(decode(&[0xff, 0xfe, b'h', 0, b'i', 0], encoding_rs::UTF_16LE), "hi");
+    }
+}
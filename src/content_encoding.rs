@@ -0,0 +1,229 @@
+use std::io::Read;
+
+/// Upper bound on a decompressed body, so a hostile capture cannot exhaust memory.
+const MAX_DECODED_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Result of undoing the `Content-Encoding` of a body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoded {
+    pub bytes: Vec<u8>,
+    /// Codings that were actually removed, in header order (e.g. `["gzip"]`).
+    pub applied: Vec<String>,
+    /// Size of the body before decompression.
+    pub encoded_size: usize,
+    /// Whether decompression stopped at `MAX_DECODED_BYTES`, leaving `bytes` incomplete.
+    pub truncated: bool,
+}
+
+impl Decoded {
+    /// Human-readable note such as `gzip 1.2 KB → 5.6 KB`, if anything was decoded.
+    pub fn summary(&self) -> Option<String> {
+        if self.applied.is_empty() {
+            return None;
+        }
+        let size = |n: usize| {
+            format!(
+                "{:.1}",
+                byte_unit::Byte::from_u64(n as u64).get_appropriate_unit(byte_unit::UnitType::Decimal)
+            )
+        };
+        Some(format!(
+            "{} {} → {}{}",
+            self.applied.join(", "),
+            size(self.encoded_size),
+            size(self.bytes.len()),
+            if self.truncated { ", truncated" } else { "" }
+        ))
+    }
+}
+
+/// Undo the codings listed in a `Content-Encoding` header value.
+///
+/// Most HAR exporters store bodies already decoded, so each coding is only
+/// removed when the bytes still look compressed; anything that fails to
+/// decompress is returned as-is.
+pub fn decode(header: Option<&str>, bytes: Vec<u8>) -> Decoded {
+    let encoded_size = bytes.len();
+    let codings: Vec<String> = header
+        .unwrap_or_default()
+        .split(',')
+        .map(|c| c.trim().to_lowercase())
+        .filter(|c| !c.is_empty() && c != "identity")
+        .collect();
+
+    let mut bytes = bytes;
+    let mut applied = Vec::new();
+    let mut truncated = false;
+    // Codings are listed in the order they were applied, so undo them in reverse.
+    for coding in codings.iter().rev() {
+        match decode_one(coding, &bytes) {
+            Some((decoded, cut)) => {
+                bytes = decoded;
+                applied.insert(0, coding.clone());
+                truncated = cut;
+            }
+            None => break,
+        }
+        // A cut-off stream cannot be decoded any further.
+        if truncated {
+            break;
+        }
+    }
+
+    Decoded {
+        bytes,
+        applied,
+        encoded_size,
+        truncated,
+    }
+}
+
+/// Decoded bytes, and whether they were cut off at `MAX_DECODED_BYTES`.
+fn decode_one(coding: &str, bytes: &[u8]) -> Option<(Vec<u8>, bool)> {
+    match coding {
+        "gzip" | "x-gzip" if bytes.starts_with(&[0x1f, 0x8b]) => {
+            read_limited(flate2::read::MultiGzDecoder::new(bytes), MAX_DECODED_BYTES)
+        }
+        "deflate" if is_zlib_header(bytes) => {
+            read_limited(flate2::read::ZlibDecoder::new(bytes), MAX_DECODED_BYTES)
+        }
+        // Some servers send raw DEFLATE without the zlib wrapper.
+        "deflate" if still_compressed(bytes) => {
+            read_limited(flate2::read::DeflateDecoder::new(bytes), MAX_DECODED_BYTES)
+        }
+        "br" if still_compressed(bytes) => {
+            read_limited(brotli_decompressor::Decompressor::new(bytes, 4096), MAX_DECODED_BYTES)
+        }
+        "zstd" if bytes.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) => {
+            read_limited(ruzstd::decoding::StreamingDecoder::new(bytes).ok()?, MAX_DECODED_BYTES)
+        }
+        _ => None,
+    }
+}
+
+/// Read at most `limit` bytes, reporting whether the reader had more.
+fn read_limited<R: Read>(reader: R, limit: u64) -> Option<(Vec<u8>, bool)> {
+    let mut out = Vec::new();
+    reader.take(limit + 1).read_to_end(&mut out).ok()?;
+    let truncated = out.len() as u64 > limit;
+    out.truncate(limit as usize);
+    Some((out, truncated))
+}
+
+/// zlib streams start with a CMF/FLG pair whose 16-bit value is a multiple of 31.
+fn is_zlib_header(bytes: &[u8]) -> bool {
+    match bytes {
+        [cmf, flg, ..] => cmf & 0x0f == 8 && (u16::from(*cmf) << 8 | u16::from(*flg)) % 31 == 0,
+        _ => false,
+    }
+}
+
+/// Brotli and raw DEFLATE have no magic number; decoded text is valid UTF-8
+/// while compressed data almost never is.
+fn still_compressed(bytes: &[u8]) -> bool {
+    !bytes.is_empty() && std::str::from_utf8(bytes).is_err()
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const BODY: &[u8] = b"{\"items\":[1,2,3],\"items2\":[1,2,3],\"items3\":[1,2,3]}";
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut enc = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        enc.write_all(data).unwrap();
+        enc.finish().unwrap()
+    }
+
+    #[test]
+    fn gzip_is_decoded_with_sizes() {
+        let compressed = gzip(BODY);
+        let decoded = decode(Some("gzip"), compressed.clone());
+        assert_eq!(decoded.bytes, BODY);
+        assert_eq!(decoded.applied, vec!["gzip"]);
+        assert_eq!(decoded.encoded_size, compressed.len());
+    }
+
+    #[test]
+    fn summary_reports_both_sizes() {
+        let decoded = decode(Some("gzip"), gzip(BODY));
+        let summary = decoded.summary().unwrap();
+        assert!(summary.starts_with("gzip "));
+        assert!(summary.ends_with(&format!("→ {} B", BODY.len())));
+        assert!(decode(None, BODY.to_vec()).summary().is_none());
+    }
+
+    #[test]
+    fn already_decoded_body_is_left_alone() {
+        let decoded = decode(Some("gzip"), BODY.to_vec());
+        assert_eq!(decoded.bytes, BODY);
+        assert!(decoded.applied.is_empty());
+
+        let decoded = decode(Some("br"), BODY.to_vec());
+        assert_eq!(decoded.bytes, BODY);
+        assert!(decoded.applied.is_empty());
+    }
+
+    #[test]
+    fn deflate_zlib_and_raw() {
+        let mut enc = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        enc.write_all(BODY).unwrap();
+        assert_eq!(decode(Some("deflate"), enc.finish().unwrap()).bytes, BODY);
+
+        let mut enc = flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::best());
+        enc.write_all(BODY).unwrap();
+        assert_eq!(decode(Some("Deflate"), enc.finish().unwrap()).bytes, BODY);
+    }
+
+    #[test]
+    fn brotli_is_decoded() {
+        // `brotli -c` of "hello" (no trailing newline), uncompressed meta-block.
+        let compressed = vec![0x0b, 0x02, 0x80, 0x68, 0x65, 0x6c, 0x6c, 0x6f, 0x03];
+        let decoded = decode(Some("br"), compressed);
+        assert_eq!(decoded.bytes, b"hello");
+        assert_eq!(decoded.applied, vec!["br"]);
+    }
+
+    #[test]
+    fn zstd_is_decoded() {
+        let compressed =
+            ruzstd::encoding::compress_to_vec(BODY, ruzstd::encoding::CompressionLevel::Fastest);
+        let decoded = decode(Some("zstd"), compressed);
+        assert_eq!(decoded.bytes, BODY);
+        assert_eq!(decoded.applied, vec!["zstd"]);
+    }
+
+    #[test]
+    fn stacked_codings_are_undone_in_reverse() {
+        let mut enc = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        enc.write_all(&gzip(BODY)).unwrap();
+        let decoded = decode(Some("gzip, deflate"), enc.finish().unwrap());
+        assert_eq!(decoded.bytes, BODY);
+        assert_eq!(decoded.applied, vec!["gzip", "deflate"]);
+    }
+
+    #[test]
+    fn oversized_output_is_flagged_as_truncated() {
+        let compressed = gzip(BODY);
+        let (bytes, truncated) = read_limited(flate2::read::MultiGzDecoder::new(&compressed[..]), 10).unwrap();
+        assert_eq!(bytes, &BODY[..10]);
+        assert!(truncated);
+
+        let limit = BODY.len() as u64;
+        let (bytes, truncated) = read_limited(flate2::read::MultiGzDecoder::new(&compressed[..]), limit).unwrap();
+        assert_eq!(bytes, BODY);
+        assert!(!truncated);
+        assert!(!decode(Some("gzip"), compressed).truncated);
+    }
+
+    #[test]
+    fn unknown_or_missing_header_is_identity() {
+        assert!(decode(None, BODY.to_vec()).applied.is_empty());
+        assert!(decode(Some("identity"), BODY.to_vec()).applied.is_empty());
+        assert!(decode(Some("compress"), vec![0x1f, 0x9d, 0x90]).applied.is_empty());
+    }
+}
@@ -0,0 +1,442 @@
+use crate::har;
+
+/// Methods a cross-origin request may use without a preflight.
+const SIMPLE_METHODS: &[&str] = &["GET", "HEAD", "POST"];
+
+/// `Content-Type` values a cross-origin request may send without a preflight.
+const SIMPLE_CONTENT_TYPES: &[&str] = &["application/x-www-form-urlencoded", "multipart/form-data", "text/plain"];
+
+/// Request headers the browser sets itself, which never trigger a preflight.
+const BROWSER_HEADERS: &[&str] = &[
+    "accept",
+    "accept-charset",
+    "accept-encoding",
+    "accept-language",
+    "cache-control",
+    "connection",
+    "content-language",
+    "content-length",
+    "content-type",
+    "cookie",
+    "dnt",
+    "host",
+    "origin",
+    "pragma",
+    "priority",
+    "referer",
+    "te",
+    "upgrade-insecure-requests",
+    "user-agent",
+];
+
+/// Response headers shown side by side, in display order.
+const RESPONSE_HEADERS: &[&str] = &[
+    "Access-Control-Allow-Origin",
+    "Access-Control-Allow-Credentials",
+    "Access-Control-Allow-Methods",
+    "Access-Control-Allow-Headers",
+    "Access-Control-Max-Age",
+    "Access-Control-Expose-Headers",
+];
+
+/// A preflight and the request it cleared, as indices into `har.log.entries`.
+/// Either side may be missing from the capture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Exchange {
+    pub preflight: Option<usize>,
+    pub actual: Option<usize>,
+}
+
+/// Whether the browser would have let the page read the response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verdict {
+    pub allowed: bool,
+    /// Why the request was blocked; empty when allowed.
+    pub reasons: Vec<String>,
+    /// Observations that do not decide the verdict.
+    pub notes: Vec<String>,
+}
+
+/// An `OPTIONS` request announcing the method of a cross-origin request.
+pub fn is_preflight(entry: &har::Entry) -> bool {
+    entry.request.method.eq_ignore_ascii_case("OPTIONS")
+        && entry.request.header("access-control-request-method").is_some()
+}
+
+/// Whether the request carries an `Origin` other than its URL's own.
+pub fn is_cross_origin(entry: &har::Entry) -> bool {
+    entry
+        .request
+        .header("origin")
+        .is_some_and(|origin| origin.trim() != entry.request.url.origin().ascii_serialization())
+}
+
+fn requested_method(preflight: &har::Entry) -> &str {
+    preflight
+        .request
+        .header("access-control-request-method")
+        .unwrap_or_default()
+        .trim()
+}
+
+/// Whether `preflight` announces `actual`: same URL, origin and method.
+fn announces(preflight: &har::Entry, actual: &har::Entry) -> bool {
+    preflight.request.url == actual.request.url
+        && preflight.request.header("origin") == actual.request.header("origin")
+        && requested_method(preflight).eq_ignore_ascii_case(&actual.request.method)
+}
+
+/// The CORS exchange the entry at `index` belongs to, or `None` for requests
+/// CORS does not apply to. A preflight is paired with the first following
+/// request it announces; a request with the closest preceding preflight, which
+/// may have been cached for several requests (`Access-Control-Max-Age`).
+pub fn exchange(entries: &[har::Entry], index: usize) -> Option<Exchange> {
+    let entry = entries.get(index)?;
+    if is_preflight(entry) {
+        let actual = entries
+            .iter()
+            .enumerate()
+            .skip(index + 1)
+            .find(|(_, e)| !is_preflight(e) && announces(entry, e))
+            .map(|(i, _)| i);
+        return Some(Exchange {
+            preflight: Some(index),
+            actual,
+        });
+    }
+    if !is_cross_origin(entry) {
+        return None;
+    }
+    let preflight = entries[..index].iter().rposition(|e| is_preflight(e) && announces(e, entry));
+    Some(Exchange {
+        preflight,
+        actual: Some(index),
+    })
+}
+
+/// Whether the request sends cookies, i.e. uses credentials mode `include`.
+fn with_credentials(entry: &har::Entry) -> bool {
+    !entry.request.cookies.is_empty() || entry.request.header("cookie").is_some()
+}
+
+/// Headers the browser would list in `Access-Control-Request-Headers` for
+/// this request, lowercased.
+fn custom_headers(entry: &har::Entry) -> Vec<String> {
+    let mut names: Vec<String> = entry
+        .request
+        .headers
+        .iter()
+        .map(|h| h.name.to_lowercase())
+        .filter(|name| {
+            !name.starts_with(':')
+                && !name.starts_with("sec-")
+                && !name.starts_with("proxy-")
+                && !BROWSER_HEADERS.contains(&name.as_str())
+        })
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Whether the request is not "simple" and so needs a preflight.
+fn needs_preflight(entry: &har::Entry) -> bool {
+    let content_type = entry
+        .request
+        .header("content-type")
+        .map(|v| v.split(';').next().unwrap_or_default().trim().to_lowercase());
+    !SIMPLE_METHODS.contains(&entry.request.method.to_uppercase().as_str())
+        || content_type.is_some_and(|ct| !SIMPLE_CONTENT_TYPES.contains(&ct.as_str()))
+        || !custom_headers(entry).is_empty()
+}
+
+fn list(value: Option<&str>) -> Vec<String> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+/// Check `Access-Control-Allow-Origin` and `-Credentials` of a response.
+fn check_allow_origin(response: &har::Response, origin: &str, credentials: bool, which: &str, reasons: &mut Vec<String>) {
+    let Some(allowed) = response.header("access-control-allow-origin").map(str::trim) else {
+        reasons.push(format!("{} response has no Access-Control-Allow-Origin", which));
+        return;
+    };
+    if allowed.contains(',') {
+        reasons.push(format!(
+            "{} response lists several origins in Access-Control-Allow-Origin; only one is allowed",
+            which
+        ));
+    } else if allowed == "*" && credentials {
+        reasons.push(format!(
+            "{} response allows origin * but the request carries credentials, which need the exact origin",
+            which
+        ));
+    } else if allowed != "*" && allowed != origin {
+        reasons.push(format!(
+            "{} response allows origin {} but the request comes from {}",
+            which, allowed, origin
+        ));
+    }
+    if credentials && response.header("access-control-allow-credentials").map(str::trim) != Some("true") {
+        reasons.push(format!(
+            "{} response lacks Access-Control-Allow-Credentials: true but the request carries credentials",
+            which
+        ));
+    }
+}
+
+/// Judge an exchange the way the browser's CORS check would. Credentials are
+/// inferred from cookies on the actual request.
+pub fn verdict(preflight: Option<&har::Entry>, actual: Option<&har::Entry>) -> Verdict {
+    let mut reasons = Vec::new();
+    let mut notes = Vec::new();
+    let origin = preflight
+        .or(actual)
+        .and_then(|e| e.request.header("origin"))
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+    let credentials = actual.is_some_and(with_credentials);
+
+    if let Some(preflight) = preflight {
+        let response = &preflight.response;
+        if !(200..300).contains(&response.status) {
+            let status = format!("{} {}", response.status, response.status_text);
+            reasons.push(format!(
+                "preflight answered {}; it must succeed with a 2xx status",
+                status.trim_end()
+            ));
+        }
+        check_allow_origin(response, &origin, credentials, "preflight", &mut reasons);
+
+        let method = requested_method(preflight);
+        let methods = list(response.header("access-control-allow-methods"));
+        let method_allowed = SIMPLE_METHODS.contains(&method)
+            || methods.iter().any(|m| m == method || (m == "*" && !credentials));
+        if !method_allowed {
+            reasons.push(format!("method {} is not in Access-Control-Allow-Methods", method));
+        }
+
+        let allowed_headers: Vec<String> = list(response.header("access-control-allow-headers"))
+            .iter()
+            .map(|h| h.to_lowercase())
+            .collect();
+        let wildcard = allowed_headers.iter().any(|h| h == "*") && !credentials;
+        for header in list(preflight.request.header("access-control-request-headers")) {
+            let header = header.to_lowercase();
+            // `*` never covers Authorization.
+            let covered = allowed_headers.contains(&header) || (wildcard && header != "authorization");
+            if !covered {
+                reasons.push(format!("header {} is not in Access-Control-Allow-Headers", header));
+            }
+        }
+    }
+
+    match actual {
+        Some(actual) if actual.response.status == 0 => {
+            reasons.push("the request failed without a response (status 0), as blocked requests are recorded".to_string());
+        }
+        Some(actual) => {
+            if preflight.is_none() && needs_preflight(actual) {
+                notes.push(
+                    "this request needs a preflight but none precedes it in the capture; it may have been cached"
+                        .to_string(),
+                );
+            }
+            check_allow_origin(&actual.response, &origin, credentials, "actual", &mut reasons);
+        }
+        None if reasons.is_empty() => {
+            notes.push("no request announced by this preflight follows it in the capture".to_string());
+        }
+        None => notes.push("the browser did not send the actual request".to_string()),
+    }
+
+    Verdict {
+        allowed: reasons.is_empty(),
+        reasons,
+        notes,
+    }
+}
+
+/// Header rows for the side-by-side view: name, preflight value, actual value.
+/// Rows absent from both entries are left out, except the allowed origin.
+pub fn header_rows(
+    preflight: Option<&har::Entry>,
+    actual: Option<&har::Entry>,
+) -> Vec<(&'static str, Option<String>, Option<String>)> {
+    let request = |entry: Option<&har::Entry>, name: &str| {
+        entry.and_then(|e| e.request.header(name)).map(str::to_string)
+    };
+    let response = |entry: Option<&har::Entry>, name: &str| {
+        entry.and_then(|e| e.response.header(name)).map(str::to_string)
+    };
+    let mut rows = vec![
+        ("Origin", request(preflight, "origin"), request(actual, "origin")),
+        (
+            "Access-Control-Request-Method",
+            request(preflight, "access-control-request-method"),
+            None,
+        ),
+        (
+            "Access-Control-Request-Headers",
+            request(preflight, "access-control-request-headers"),
+            None,
+        ),
+    ];
+    for name in RESPONSE_HEADERS {
+        rows.push((name, response(preflight, name), response(actual, name)));
+    }
+    rows.retain(|(name, pre, act)| pre.is_some() || act.is_some() || *name == "Access-Control-Allow-Origin");
+    rows
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(method: &str, url: &str, request: &[(&str, &str)], status: i64, response: &[(&str, &str)]) -> har::Entry {
+        har::Entry::test(method, url, status)
+            .with_request_headers(request)
+            .with_response_headers(response)
+    }
+
+    const API: &str = "https://api.test/items";
+    const APP: &str = "https://app.test";
+
+    fn preflight(response: &[(&str, &str)]) -> har::Entry {
+        entry(
+            "OPTIONS",
+            API,
+            &[
+                ("Origin", APP),
+                ("Access-Control-Request-Method", "PUT"),
+                ("Access-Control-Request-Headers", "content-type,x-token"),
+            ],
+            204,
+            response,
+        )
+    }
+
+    fn put(response: &[(&str, &str)]) -> har::Entry {
+        entry(
+            "PUT",
+            API,
+            &[("Origin", APP), ("Content-Type", "application/json"), ("X-Token", "t")],
+            200,
+            response,
+        )
+    }
+
+    #[test]
+    fn pairs_preflights_with_the_requests_they_announce() {
+        let entries = vec![
+            preflight(&[]),
+            entry("GET", "https://app.test/", &[], 200, &[]),
+            entry("GET", API, &[("Origin", APP)], 200, &[]),
+            put(&[]),
+            put(&[]),
+            entry("POST", "https://app.test/form", &[("Origin", APP)], 200, &[]),
+        ];
+        let both = Exchange {
+            preflight: Some(0),
+            actual: Some(3),
+        };
+        assert_eq!(exchange(&entries, 0), Some(both));
+        assert_eq!(exchange(&entries, 3), Some(both));
+        // A cached preflight clears later requests too.
+        assert_eq!(exchange(&entries, 4).unwrap().preflight, Some(0));
+        assert_eq!(
+            exchange(&entries, 2),
+            Some(Exchange {
+                preflight: None,
+                actual: Some(2)
+            })
+        );
+        // Same-origin requests and requests without Origin are not CORS.
+        assert_eq!(exchange(&entries, 1), None);
+        assert_eq!(exchange(&entries, 5), None);
+    }
+
+    #[test]
+    fn allows_a_well_answered_exchange() {
+        let pre = preflight(&[
+            ("Access-Control-Allow-Origin", APP),
+            ("Access-Control-Allow-Methods", "GET, PUT"),
+            ("Access-Control-Allow-Headers", "Content-Type, X-Token"),
+        ]);
+        let actual = put(&[("Access-Control-Allow-Origin", "*")]);
+        let verdict = verdict(Some(&pre), Some(&actual));
+        assert!(verdict.allowed, "{:?}", verdict.reasons);
+        assert!(verdict.notes.is_empty());
+    }
+
+    #[test]
+    fn explains_why_a_preflight_fails() {
+        let pre = preflight(&[
+            ("Access-Control-Allow-Origin", "https://other.test"),
+            ("Access-Control-Allow-Methods", "GET, POST"),
+            ("Access-Control-Allow-Headers", "content-type"),
+        ]);
+        let verdict = verdict(Some(&pre), None);
+        assert!(!verdict.allowed);
+        assert_eq!(
+            verdict.reasons,
+            vec![
+                "preflight response allows origin https://other.test but the request comes from https://app.test",
+                "method PUT is not in Access-Control-Allow-Methods",
+                "header x-token is not in Access-Control-Allow-Headers",
+            ]
+        );
+        assert_eq!(verdict.notes, vec!["the browser did not send the actual request"]);
+    }
+
+    #[test]
+    fn credentials_rule_out_wildcards() {
+        let pre = preflight(&[
+            ("Access-Control-Allow-Origin", "*"),
+            ("Access-Control-Allow-Methods", "*"),
+            ("Access-Control-Allow-Headers", "*"),
+        ]);
+        let mut actual = put(&[("Access-Control-Allow-Origin", APP)]);
+        actual.request.headers.push(har::Header {
+            name: "Cookie".to_string(),
+            value: "session=1".to_string(),
+            extra: Default::default(),
+        });
+        let verdict = verdict(Some(&pre), Some(&actual));
+        assert_eq!(verdict.reasons.len(), 6, "{:#?}", verdict.reasons);
+        assert!(verdict.reasons[0].contains("allows origin *"));
+        assert!(verdict.reasons[5].starts_with("actual response lacks Access-Control-Allow-Credentials"));
+    }
+
+    #[test]
+    fn notes_uncaptured_preflights_and_blocked_requests() {
+        let actual = put(&[("Access-Control-Allow-Origin", APP)]);
+        let verdict = verdict(None, Some(&actual));
+        assert!(verdict.allowed);
+        assert!(verdict.notes[0].contains("needs a preflight"));
+
+        let mut blocked = put(&[]);
+        blocked.response.status = 0;
+        assert!(super::verdict(None, Some(&blocked)).reasons[0].contains("status 0"));
+
+        let rows = header_rows(Some(&preflight(&[])), Some(&actual));
+        let names: Vec<_> = rows.iter().map(|(name, _, _)| *name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "Origin",
+                "Access-Control-Request-Method",
+                "Access-Control-Request-Headers",
+                "Access-Control-Allow-Origin"
+            ]
+        );
+        assert_eq!(rows[3], ("Access-Control-Allow-Origin", None, Some(APP.to_string())));
+    }
+}
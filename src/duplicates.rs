@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use crate::caching;
+use crate::entry_diff;
+use crate::har;
+
+/// Query parameters that only defeat caches; they are ignored when comparing
+/// URLs, so `?_=1717243200123` requests count as duplicates.
+const CACHE_BUSTERS: &[&str] = &["_", "_t", "_ts", "cb", "cachebust", "cachebuster", "nocache", "rnd", "timestamp"];
+
+/// Requests that ask the server for the same thing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    pub method: String,
+    /// Normalized URL shared by the group.
+    pub url: String,
+    /// Indices into `har.log.entries`, in capture order.
+    pub entries: Vec<usize>,
+    /// Whether URLs and bodies are byte-for-byte identical, not just equal
+    /// after normalization.
+    pub identical: bool,
+    /// Response bytes of every request after the first.
+    pub wasted_bytes: u64,
+    /// Milliseconds spent on every request after the first.
+    pub wasted_ms: u64,
+}
+
+/// `url` without fragment and cache busters, with the query sorted.
+pub fn normalized_url(url: &url::Url) -> String {
+    let mut url = url.clone();
+    url.set_fragment(None);
+    let mut pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(name, _)| !CACHE_BUSTERS.contains(&name.as_ref()))
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+    pairs.sort();
+    if pairs.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    url.to_string()
+}
+
+/// Request body with JSON keys and form fields in a canonical order.
+pub fn normalized_body(entry: &har::Entry) -> String {
+    let Some(post_data) = &entry.request.post_data else {
+        return String::new();
+    };
+    let text = post_data.text.trim();
+    if let Ok(json) = serde_json::from_str::<serde_json::Value>(text) {
+        return entry_diff::sort_keys(json).to_string();
+    }
+    if post_data.mime_type.starts_with("application/x-www-form-urlencoded") {
+        let mut pairs: Vec<_> = url::form_urlencoded::parse(text.as_bytes()).collect();
+        pairs.sort();
+        return url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(pairs)
+            .finish();
+    }
+    text.to_string()
+}
+
+fn raw_key(entry: &har::Entry) -> (&str, &str) {
+    let body = entry.request.post_data.as_ref().map_or("", |p| p.text.as_str());
+    (entry.request.url.as_str(), body)
+}
+
+/// Groups of two or more requests with the same method, normalized URL and
+/// normalized body, most repeated first. Responses the browser served from
+/// its cache never reached the network and are left out.
+pub fn find(entries: &[har::Entry]) -> Vec<Group> {
+    let mut order: Vec<(String, String, String)> = Vec::new();
+    let mut groups: HashMap<(String, String, String), Vec<usize>> = HashMap::new();
+    for (index, entry) in entries.iter().enumerate() {
+        if entry.from_cache.as_deref().is_some_and(|s| !s.is_empty()) {
+            continue;
+        }
+        let key = (
+            entry.request.method.to_uppercase(),
+            normalized_url(&entry.request.url),
+            normalized_body(entry),
+        );
+        if !groups.contains_key(&key) {
+            order.push(key.clone());
+        }
+        groups.entry(key).or_default().push(index);
+    }
+
+    let mut found: Vec<Group> = order
+        .into_iter()
+        .filter_map(|key| {
+            let indices = groups.remove(&key)?;
+            if indices.len() < 2 {
+                return None;
+            }
+            let first = raw_key(&entries[indices[0]]);
+            let later = || indices[1..].iter().map(|&i| &entries[i]);
+            let (method, url, _) = key;
+            Some(Group {
+                method,
+                url,
+                identical: later().all(|e| raw_key(e) == first),
+                wasted_bytes: later().map(caching::transferred).sum(),
+                wasted_ms: later().map(|e| e.time.max(0.0)).sum::<f64>().round() as u64,
+                entries: indices,
+            })
+        })
+        .collect();
+    found.sort_by(|a, b| {
+        b.entries
+            .len()
+            .cmp(&a.entries.len())
+            .then(b.wasted_ms.cmp(&a.wasted_ms))
+    });
+    found
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(method: &str, url: &str, body: Option<(&str, &str)>, time: f64) -> har::Entry {
+        let mut entry = har::Entry::test(method, url, 200).with_time(time);
+        if let Some((mime_type, text)) = body {
+            entry = entry.with_request_body(mime_type, text);
+        }
+        entry.response.content.size = Some(500);
+        entry.response.body_size = Some(300);
+        entry
+    }
+
+    #[test]
+    fn normalizes_urls_and_bodies() {
+        let url = url::Url::parse("https://x.test/api?b=2&_=1717243200123&a=1#top").unwrap();
+        assert_eq!(normalized_url(&url), "https://x.test/api?a=1&b=2");
+        let bare = url::Url::parse("https://x.test/api?cb=9").unwrap();
+        assert_eq!(normalized_url(&bare), "https://x.test/api");
+
+        let json = entry("POST", "https://x.test/", Some(("application/json", "{\"b\": 1, \"a\": [2]}")), 1.0);
+        assert_eq!(normalized_body(&json), "{\"a\":[2],\"b\":1}");
+        let form = entry(
+            "POST",
+            "https://x.test/",
+            Some(("application/x-www-form-urlencoded; charset=UTF-8", "z=1&a=x+y")),
+            1.0,
+        );
+        assert_eq!(normalized_body(&form), "a=x+y&z=1");
+    }
+
+    #[test]
+    fn groups_identical_and_near_identical_requests() {
+        let mut cached = entry("GET", "https://x.test/api/user", None, 1.0);
+        cached.from_cache = Some("memory".to_string());
+        let entries = vec![
+            entry("GET", "https://x.test/api/user", None, 100.0),
+            entry("POST", "https://x.test/api/track", Some(("application/json", "{\"e\":1,\"p\":2}")), 40.0),
+            entry("GET", "https://x.test/api/user", None, 120.0),
+            entry("POST", "https://x.test/api/track", Some(("application/json", "{\"p\":2,\"e\":1}")), 60.0),
+            entry("GET", "https://x.test/api/user?_=123", None, 80.5),
+            entry("POST", "https://x.test/api/track", Some(("application/json", "{\"e\":2}")), 10.0),
+            entry("GET", "https://x.test/api/other", None, 10.0),
+            cached,
+        ];
+        let groups = find(&entries);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(
+            groups[0],
+            Group {
+                method: "GET".to_string(),
+                url: "https://x.test/api/user".to_string(),
+                entries: vec![0, 2, 4],
+                identical: false,
+                wasted_bytes: 600,
+                wasted_ms: 201,
+            }
+        );
+        assert_eq!(groups[1].entries, vec![1, 3]);
+        assert!(!groups[1].identical);
+        assert!(find(&entries[..3]).iter().all(|g| g.identical));
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::caching;
+use crate::har;
+
+/// Path segments that identify a resource rather than name an endpoint.
+static ID_SEGMENT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?ix)^(?:
+            \d+                                                    # numeric id
+            | [0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}  # UUID
+            | [0-9a-f]{16,}                                        # hex hash or object id
+        )$",
+    )
+    .expect("valid id pattern")
+});
+
+/// Stand-in for a templated path segment.
+pub const ID: &str = "{id}";
+
+/// Whether a path segment is an id. Hex-looking words such as `cafe` or
+/// `deadbeef` are too short to count; long hex strings need a digit.
+pub fn is_id(segment: &str) -> bool {
+    ID_SEGMENT.is_match(segment) && segment.bytes().any(|b| b.is_ascii_digit())
+}
+
+/// `url` without query and fragment, with id segments replaced by `{id}`.
+pub fn template(url: &url::Url) -> String {
+    let path: Vec<&str> = url
+        .path()
+        .split('/')
+        .map(|segment| if is_id(segment) { ID } else { segment })
+        .collect();
+    format!("{}{}", url.origin().ascii_serialization(), path.join("/"))
+}
+
+/// Requests to one API endpoint and how they performed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Endpoint {
+    pub method: String,
+    pub template: String,
+    /// Indices into `har.log.entries`, in capture order.
+    pub entries: Vec<usize>,
+    /// Number of responses per status code.
+    pub statuses: BTreeMap<i64, usize>,
+    /// Percentiles of `Entry.time`, in milliseconds.
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+    pub max: f64,
+    /// Mean response bytes transferred.
+    pub avg_size: u64,
+}
+
+/// Nearest-rank percentile of sorted `values`.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Entries grouped by method and URL template, busiest endpoints first.
+pub fn inventory(entries: &[har::Entry]) -> Vec<Endpoint> {
+    let mut groups: HashMap<(String, String), Vec<usize>> = HashMap::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let key = (entry.request.method.to_uppercase(), template(&entry.request.url));
+        groups.entry(key).or_default().push(index);
+    }
+
+    let mut endpoints: Vec<Endpoint> = groups
+        .into_iter()
+        .map(|((method, template), indices)| {
+            let group = || indices.iter().map(|&i| &entries[i]);
+            let mut times: Vec<f64> = group().map(|e| e.time.max(0.0)).collect();
+            times.sort_by(f64::total_cmp);
+            let mut statuses = BTreeMap::new();
+            for entry in group() {
+                *statuses.entry(entry.response.status).or_default() += 1;
+            }
+            let bytes: u64 = group().map(caching::transferred).sum();
+            Endpoint {
+                method,
+                template,
+                statuses,
+                p50: percentile(&times, 50.0),
+                p90: percentile(&times, 90.0),
+                p99: percentile(&times, 99.0),
+                max: times.last().copied().unwrap_or(0.0),
+                avg_size: bytes / indices.len() as u64,
+                entries: indices,
+            }
+        })
+        .collect();
+    endpoints.sort_by(|a, b| {
+        b.entries
+            .len()
+            .cmp(&a.entries.len())
+            .then_with(|| a.template.cmp(&b.template))
+            .then_with(|| a.method.cmp(&b.method))
+    });
+    endpoints
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(method: &str, url: &str, status: i64, time: f64, size: i64) -> har::Entry {
+        let mut entry = har::Entry::test(method, url, status).with_time(time);
+        entry.response.content.size = Some(size);
+        entry.response.body_size = Some(size);
+        entry
+    }
+
+    #[test]
+    fn templates_ids_uuids_and_hashes() {
+        let url = |s: &str| url::Url::parse(s).unwrap();
+        assert_eq!(
+            template(&url("https://api.test/users/42/orders/123e4567-e89b-12d3-a456-426614174000?x=1")),
+            "https://api.test/users/{id}/orders/{id}"
+        );
+        assert_eq!(
+            template(&url("https://api.test/blobs/5f3a9c1e2b7d4e8f9a0b1c2d/v2")),
+            "https://api.test/blobs/{id}/v2"
+        );
+        // Versions, words and short hex-looking names stay.
+        assert_eq!(template(&url("https://api.test/v1/cafe/deadbeefdeadbeef/")), "https://api.test/v1/cafe/deadbeefdeadbeef/");
+        assert_eq!(template(&url("http://localhost:8080/")), "http://localhost:8080/");
+    }
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let sorted: Vec<f64> = (1..=10).map(f64::from).collect();
+        assert_eq!(percentile(&sorted, 50.0), 5.0);
+        assert_eq!(percentile(&sorted, 90.0), 9.0);
+        assert_eq!(percentile(&sorted, 99.0), 10.0);
+        assert_eq!(percentile(&[7.0], 50.0), 7.0);
+        assert_eq!(percentile(&[], 50.0), 0.0);
+    }
+
+    #[test]
+    fn groups_entries_into_endpoints() {
+        let entries = vec![
+            entry("GET", "https://api.test/users/1", 200, 30.0, 100),
+            entry("GET", "https://api.test/users/2?fields=name", 200, 10.0, 300),
+            entry("DELETE", "https://api.test/users/2", 204, 5.0, 0),
+            entry("GET", "https://api.test/users/3", 404, 20.0, 50),
+            entry("GET", "https://api.test/users", 200, 90.0, 1000),
+        ];
+        let endpoints = inventory(&entries);
+        let keys: Vec<_> = endpoints
+            .iter()
+            .map(|e| (e.method.as_str(), e.template.as_str(), e.entries.len()))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                ("GET", "https://api.test/users/{id}", 3),
+                ("GET", "https://api.test/users", 1),
+                ("DELETE", "https://api.test/users/{id}", 1),
+            ]
+        );
+        let users = &endpoints[0];
+        assert_eq!(users.entries, vec![0, 1, 3]);
+        assert_eq!(users.statuses, BTreeMap::from([(200, 2), (404, 1)]));
+        assert_eq!((users.p50, users.p90, users.p99, users.max), (20.0, 30.0, 30.0, 30.0));
+        assert_eq!(users.avg_size, 150);
+    }
+}
@@ -0,0 +1,356 @@
+use similar::{DiffTag, TextDiff};
+use std::time::Duration;
+
+use crate::app;
+use crate::har;
+use crate::hexdump;
+
+/// Lines of unchanged context kept around each body change.
+const CONTEXT_LINES: usize = 3;
+
+/// Give up on finding a minimal diff of very large bodies after this long.
+const DIFF_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// How one side of a diff row relates to the other side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Same,
+    Removed,
+    Added,
+    Changed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    pub text: String,
+    pub change: Change,
+}
+
+impl Cell {
+    pub fn new(text: impl Into<String>, change: Change) -> Self {
+        Self {
+            text: text.into(),
+            change,
+        }
+    }
+}
+
+/// One row of the side-by-side view; the left side is the marked entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffRow {
+    Section(&'static str),
+    Pair(Option<Cell>, Option<Cell>),
+    /// Run of unchanged body lines that was skipped.
+    Gap(usize),
+    Note(String),
+}
+
+impl DiffRow {
+    pub fn is_change(&self) -> bool {
+        match self {
+            Self::Pair(left, right) => [left, right]
+                .iter()
+                .any(|c| c.as_ref().is_some_and(|c| c.change != Change::Same)),
+            _ => false,
+        }
+    }
+}
+
+/// Side-by-side comparison of two entries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryDiff {
+    pub rows: Vec<DiffRow>,
+    /// Number of rows that differ.
+    pub changes: usize,
+}
+
+pub fn diff_entries(left: &har::Entry, right: &har::Entry) -> EntryDiff {
+    let mut rows = vec![DiffRow::Section("Overview")];
+    rows.push(pair_row(
+        &format!("{} {}", left.request.method, left.request.url),
+        &format!("{} {}", right.request.method, right.request.url),
+    ));
+    rows.push(pair_row(
+        &format!("{} {}", left.response.status, left.response.status_text),
+        &format!("{} {}", right.response.status, right.response.status_text),
+    ));
+    rows.push(DiffRow::Pair(
+        Some(Cell::new(format!("{:.0} ms", left.time), Change::Same)),
+        Some(Cell::new(format!("{:.0} ms", right.time), Change::Same)),
+    ));
+
+    rows.push(DiffRow::Section("Request headers"));
+    rows.extend(field_diff(
+        &header_pairs(&left.request.headers),
+        &header_pairs(&right.request.headers),
+    ));
+    rows.push(DiffRow::Section("Query parameters"));
+    let query = |e: &har::Entry| -> Vec<(String, String)> {
+        e.request
+            .query_string
+            .iter()
+            .map(|q| (q.name.clone(), q.value.clone()))
+            .collect()
+    };
+    rows.extend(field_diff(&query(left), &query(right)));
+    rows.push(DiffRow::Section("Request body"));
+    let request_body = |e: &har::Entry| e.request.post_data.as_ref().map(|p| p.text.clone());
+    rows.extend(body_diff(request_body(left).as_deref(), request_body(right).as_deref()));
+
+    rows.push(DiffRow::Section("Response headers"));
+    rows.extend(field_diff(
+        &header_pairs(&left.response.headers),
+        &header_pairs(&right.response.headers),
+    ));
+    rows.push(DiffRow::Section("Response body"));
+    rows.extend(response_body_diff(left, right));
+
+    let changes = rows.iter().filter(|r| r.is_change()).count();
+    EntryDiff { rows, changes }
+}
+
+fn pair_row(left: &str, right: &str) -> DiffRow {
+    let change = if left == right { Change::Same } else { Change::Changed };
+    DiffRow::Pair(Some(Cell::new(left, change)), Some(Cell::new(right, change)))
+}
+
+/// Headers with lowercased names, so the comparison ignores name casing.
+fn header_pairs(headers: &[har::Header]) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|h| (h.name.to_lowercase(), h.value.clone()))
+        .collect()
+}
+
+/// Added, removed and changed `name: value` fields. Repeated names are
+/// compared as one comma-joined value; identical fields are only counted.
+pub fn field_diff(left: &[(String, String)], right: &[(String, String)]) -> Vec<DiffRow> {
+    let collect = |fields: &[(String, String)]| {
+        let mut grouped: Vec<(String, String)> = Vec::new();
+        for (name, value) in fields {
+            match grouped.iter_mut().find(|(n, _)| n == name) {
+                Some((_, joined)) => {
+                    joined.push_str(", ");
+                    joined.push_str(value);
+                }
+                None => grouped.push((name.clone(), value.clone())),
+            }
+        }
+        grouped
+    };
+    let left = collect(left);
+    let right = collect(right);
+    let lookup = |fields: &[(String, String)], name: &str| {
+        fields.iter().find(|(n, _)| n == name).map(|(_, v)| v.clone())
+    };
+
+    let mut rows = Vec::new();
+    let mut identical = 0;
+    for (name, value) in &left {
+        let text = format!("{}: {}", name, value);
+        match lookup(&right, name) {
+            Some(other) if other == *value => identical += 1,
+            Some(other) => rows.push(DiffRow::Pair(
+                Some(Cell::new(text, Change::Changed)),
+                Some(Cell::new(format!("{}: {}", name, other), Change::Changed)),
+            )),
+            None => rows.push(DiffRow::Pair(Some(Cell::new(text, Change::Removed)), None)),
+        }
+    }
+    for (name, value) in &right {
+        if lookup(&left, name).is_none() {
+            rows.push(DiffRow::Pair(
+                None,
+                Some(Cell::new(format!("{}: {}", name, value), Change::Added)),
+            ));
+        }
+    }
+    match (rows.is_empty(), identical) {
+        (true, 0) => rows.push(DiffRow::Note("none".to_string())),
+        (_, 0) => {}
+        (_, n) => rows.push(DiffRow::Note(format!("{} identical", n))),
+    }
+    rows
+}
+
+fn response_body_diff(left: &har::Entry, right: &har::Entry) -> Vec<DiffRow> {
+    let is_binary = |e: &har::Entry| {
+        let mime = e.response.content.mime_type.as_deref().unwrap_or_default();
+        app::decode_response(e).is_some_and(|d| {
+            hexdump::is_binary(mime, &d.bytes) && app::response_charset(e, &d) == encoding_rs::UTF_8
+        })
+    };
+    if is_binary(left) || is_binary(right) {
+        let bytes = |e: &har::Entry| app::decode_response(e).map(|d| d.bytes).unwrap_or_default();
+        let (l, r) = (bytes(left), bytes(right));
+        let note = if l == r {
+            format!("binary, identical ({} bytes)", l.len())
+        } else {
+            format!("binary bodies differ ({} vs {} bytes)", l.len(), r.len())
+        };
+        return vec![DiffRow::Note(note)];
+    }
+    body_diff(
+        app::response_text(left).as_deref(),
+        app::response_text(right).as_deref(),
+    )
+}
+
+/// Body text prepared for a line diff: JSON is pretty-printed with object keys
+/// sorted so key order does not show up as a difference.
+pub fn normalize_body(text: &str) -> String {
+    match app::parse_json(text) {
+        Some(value) => serde_json::to_string_pretty(&sort_keys(value)).unwrap_or_default(),
+        None => text.to_string(),
+    }
+}
+
+/// `value` with object keys sorted at every depth.
+pub fn sort_keys(value: serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Object(entries.into_iter().map(|(k, v)| (k, sort_keys(v))).collect())
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        other => other,
+    }
+}
+
+/// Line-level diff of two bodies with a few lines of context around changes.
+pub fn body_diff(left: Option<&str>, right: Option<&str>) -> Vec<DiffRow> {
+    let (left, right) = match (left, right) {
+        (None, None) => return vec![DiffRow::Note("none".to_string())],
+        (l, r) => (normalize_body(l.unwrap_or_default()), normalize_body(r.unwrap_or_default())),
+    };
+    let diff = TextDiff::configure()
+        .timeout(DIFF_TIMEOUT)
+        .diff_lines(&left, &right);
+    let old = diff.old_slices();
+    let new = diff.new_slices();
+    let line = |s: &str| s.trim_end_matches(['\n', '\r']).to_string();
+
+    let groups = diff.grouped_ops(CONTEXT_LINES);
+    if groups.is_empty() {
+        return vec![DiffRow::Note(format!("identical ({} lines)", old.len()))];
+    }
+
+    let mut rows = Vec::new();
+    let mut shown_until = 0;
+    for group in &groups {
+        let start = group[0].old_range().start;
+        if start > shown_until {
+            rows.push(DiffRow::Gap(start - shown_until));
+        }
+        for op in group {
+            let (tag, old_range, new_range) = op.as_tag_tuple();
+            match tag {
+                DiffTag::Equal => rows.extend(old_range.clone().map(|i| {
+                    DiffRow::Pair(
+                        Some(Cell::new(line(old[i]), Change::Same)),
+                        Some(Cell::new(line(old[i]), Change::Same)),
+                    )
+                })),
+                _ => {
+                    let len = old_range.len().max(new_range.len());
+                    rows.extend((0..len).map(|k| {
+                        let l = old.get(old_range.start + k).filter(|_| k < old_range.len());
+                        let r = new.get(new_range.start + k).filter(|_| k < new_range.len());
+                        DiffRow::Pair(
+                            l.map(|s| Cell::new(line(s), Change::Removed)),
+                            r.map(|s| Cell::new(line(s), Change::Added)),
+                        )
+                    }));
+                }
+            }
+            shown_until = old_range.end;
+        }
+    }
+    if old.len() > shown_until {
+        rows.push(DiffRow::Gap(old.len() - shown_until));
+    }
+    rows
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(n, v)| (n.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn field_diff_reports_added_removed_changed() {
+        let rows = field_diff(
+            &fields(&[("a", "1"), ("b", "2"), ("c", "3")]),
+            &fields(&[("a", "1"), ("b", "x"), ("d", "4")]),
+        );
+        assert_eq!(
+            rows,
+            vec![
+                DiffRow::Pair(
+                    Some(Cell::new("b: 2", Change::Changed)),
+                    Some(Cell::new("b: x", Change::Changed))
+                ),
+                DiffRow::Pair(Some(Cell::new("c: 3", Change::Removed)), None),
+                DiffRow::Pair(None, Some(Cell::new("d: 4", Change::Added))),
+                DiffRow::Note("1 identical".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn field_diff_joins_repeated_names() {
+        let rows = field_diff(
+            &fields(&[("set-cookie", "a"), ("set-cookie", "b")]),
+            &fields(&[("set-cookie", "a, b")]),
+        );
+        assert_eq!(rows, vec![DiffRow::Note("1 identical".to_string())]);
+    }
+
+    #[test]
+    fn json_bodies_ignore_key_order() {
+        let rows = body_diff(Some(r#"{"a":1,"b":[1,2]}"#), Some(r#"{"b":[1,2],"a":1}"#));
+        assert!(matches!(&rows[..], [DiffRow::Note(n)] if n.starts_with("identical")));
+        // Parsing keeps document order, so nested objects need sorting too.
+        assert_eq!(
+            normalize_body(r#"{"z":{"y":1,"x":[{"b":2,"a":1}]},"a":0}"#),
+            normalize_body(r#"{"a":0,"z":{"x":[{"a":1,"b":2}],"y":1}}"#)
+        );
+    }
+
+    #[test]
+    fn json_body_change_is_a_line_pair() {
+        let rows = body_diff(Some(r#"{"a":1,"b":2}"#), Some(r#"{"b":2,"a":3}"#));
+        assert!(rows.contains(&DiffRow::Pair(
+            Some(Cell::new("  \"a\": 1,", Change::Removed)),
+            Some(Cell::new("  \"a\": 3,", Change::Added)),
+        )));
+    }
+
+    #[test]
+    fn long_unchanged_runs_become_gaps() {
+        let left: Vec<String> = (0..40).map(|i| format!("line {}", i)).collect();
+        let mut right = left.clone();
+        right[20] = "changed".to_string();
+        let rows = body_diff(Some(&left.join("\n")), Some(&right.join("\n")));
+        assert_eq!(rows.first(), Some(&DiffRow::Gap(17)));
+        assert_eq!(rows.last(), Some(&DiffRow::Gap(16)));
+        assert_eq!(rows.iter().filter(|r| r.is_change()).count(), 1);
+    }
+
+    #[test]
+    fn missing_bodies() {
+        assert_eq!(body_diff(None, None), vec![DiffRow::Note("none".to_string())]);
+        let rows = body_diff(None, Some("x"));
+        assert_eq!(rows, vec![DiffRow::Pair(None, Some(Cell::new("x", Change::Added)))]);
+    }
+}
@@ -0,0 +1,100 @@
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::har::{self, Har};
+
+/// How often the followed file is checked for changes.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Entries of a reloaded capture that were not seen before. Files are treated
+/// as append-only, like `tail -f`: a capture that shrank (rewritten from
+/// scratch) yields nothing until it grows past what was already shown.
+pub fn new_entries(known: usize, har: Har) -> Vec<har::Entry> {
+    har.log.entries.into_iter().skip(known).collect()
+}
+
+/// Poll `path` and send entries appended to it, starting after the first
+/// `known`. Reloads happen when the size or modification time changes;
+/// unparsable states (a writer halfway through) are skipped until the next
+/// change. Returns when the receiver is dropped.
+pub async fn watch(path: PathBuf, mut known: usize, tx: UnboundedSender<har::Entry>, interval: Duration) {
+    let stamp = |path: &PathBuf| -> Option<(u64, SystemTime)> {
+        let meta = std::fs::metadata(path).ok()?;
+        Some((meta.len(), meta.modified().ok()?))
+    };
+    // Start unset so the first tick catches anything written since loading.
+    let mut last = None;
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        if tx.is_closed() {
+            return;
+        }
+        let current = stamp(&path);
+        if current.is_none() || current == last {
+            continue;
+        }
+        let Ok(har) = Har::from_file(&path).await else {
+            continue;
+        };
+        last = current;
+        let total = har.log.entries.len();
+        for entry in new_entries(known, har) {
+            if tx.send(entry).is_err() {
+                return;
+            }
+        }
+        known = known.max(total);
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str) -> serde_json::Value {
+        serde_json::to_value(har::Entry::test("GET", &format!("https://x.test{}", path), 200)).unwrap()
+    }
+
+    fn write(path: &std::path::Path, paths: &[&str]) {
+        let entries: Vec<_> = paths.iter().map(|p| entry(p)).collect();
+        let har = serde_json::json!({"log": {"version": "1.2", "entries": entries}});
+        std::fs::write(path, har.to_string()).unwrap();
+    }
+
+    #[test]
+    fn only_unseen_entries_are_new() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        write(file.path(), &["/a", "/b", "/c"]);
+        let har: Har = serde_json::from_str(&std::fs::read_to_string(file.path()).unwrap()).unwrap();
+        let new = new_entries(2, har.clone());
+        assert_eq!(new.len(), 1);
+        assert_eq!(new[0].request.url.path(), "/c");
+        assert!(new_entries(5, har).is_empty());
+    }
+
+    #[tokio::test]
+    async fn appended_entries_are_sent() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        write(file.path(), &["/a"]);
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let task = tokio::spawn(watch(file.path().to_path_buf(), 1, tx, Duration::from_millis(10)));
+
+        // A half-written file is skipped, then picked up once complete.
+        std::fs::write(file.path(), r#"{"log": {"entries": ["#).unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        write(file.path(), &["/a", "/b", "/c"]);
+
+        let b = rx.recv().await.unwrap();
+        let c = rx.recv().await.unwrap();
+        assert_eq!((b.request.url.path(), c.request.url.path()), ("/b", "/c"));
+        assert!(rx.try_recv().is_err());
+
+        drop(rx);
+        task.await.unwrap();
+    }
+}
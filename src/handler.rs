@@ -18,7 +18,59 @@ pub enum Command {
     TabNext,
     TabPrev,
     ToggleSyntaxHighlighting,
+    ToggleHexView,
     SetTableIndex(usize),
+    // Hex view goto-offset prompt
+    EnterOffsetInput,
+    OffsetChar(char),
+    OffsetBackspace,
+    OffsetConfirm,
+    OffsetCancel,
+    // JSON tree view
+    ToggleTreeView,
+    TreeMove(i32),
+    TreeFirst,
+    TreeLast,
+    TreeToggle,
+    TreeExpand,
+    TreeCollapse,
+    TreeExpandToDepth(usize),
+    TreeExpandAll,
+    TreeCopy,
+    TreeFindStart,
+    TreeFindChar(char),
+    TreeFindBackspace,
+    TreeFindConfirm,
+    TreeFindCancel,
+    TreeFindNext,
+    // jq/JSONPath body query prompt
+    EnterBodyQuery,
+    BodyQueryChar(char),
+    BodyQueryBackspace,
+    BodyQueryConfirm,
+    BodyQueryCancel,
+    ClearBodyQuery,
+    // Entry diff
+    ToggleMark,
+    OpenDiff,
+    // In-preview search
+    EnterPreviewSearch,
+    PreviewSearchChar(char),
+    PreviewSearchBackspace,
+    PreviewSearchConfirm,
+    PreviewSearchCancel,
+    PreviewMatchNext,
+    PreviewMatchPrev,
+    ClearPreviewSearch,
+    // Replay
+    EnterReplay,
+    ReplayChar(char),
+    ReplayBackspace,
+    ReplayConfirm,
+    ReplayCancel,
+    EditReplay,
+    ExportSanitized,
+    ToggleDuplicatesOnly,
     // Search
     EnterSearchMode,
     SearchChar(char),
@@ -53,7 +105,55 @@ impl Command {
             Self::TabNext => app.next_tab(),
             Self::TabPrev => app.prev_tab(),
             Self::ToggleSyntaxHighlighting => app.toggle_syntax_highlighting(),
+            Self::ToggleHexView => app.toggle_hex_view(),
             Self::SetTableIndex(index) => app.update_index_absolute(*index),
+            Self::EnterOffsetInput => app.enter_offset_input(),
+            Self::OffsetChar(c) => app.push_offset_char(*c),
+            Self::OffsetBackspace => app.pop_offset_char(),
+            Self::OffsetConfirm => app.confirm_offset_input(),
+            Self::OffsetCancel => app.cancel_offset_input(),
+            Self::ToggleTreeView => app.toggle_tree_view(),
+            Self::TreeMove(delta) => app.update_tree(|t| t.move_cursor(*delta)),
+            Self::TreeFirst => app.update_tree(|t| t.cursor_first()),
+            Self::TreeLast => app.update_tree(|t| t.cursor_last()),
+            Self::TreeToggle => app.update_tree(|t| t.toggle()),
+            Self::TreeExpand => app.update_tree(|t| t.expand()),
+            Self::TreeCollapse => app.update_tree(|t| t.collapse()),
+            Self::TreeExpandToDepth(depth) => app.update_tree(|t| t.expand_to_depth(*depth)),
+            Self::TreeExpandAll => app.update_tree(|t| t.expand_all()),
+            Self::TreeCopy => app.copy_tree_subtree(),
+            Self::TreeFindStart => app.update_tree(|t| t.enter_find()),
+            Self::TreeFindChar(c) => app.update_tree(|t| t.push_find_char(*c)),
+            Self::TreeFindBackspace => app.update_tree(|t| t.pop_find_char()),
+            Self::TreeFindConfirm => app.update_tree(|t| t.confirm_find()),
+            Self::TreeFindCancel => app.update_tree(|t| t.cancel_find()),
+            Self::TreeFindNext => app.update_tree(|t| t.find_next()),
+            Self::EnterBodyQuery => app.enter_body_query(),
+            Self::BodyQueryChar(c) => app.push_body_query_char(*c),
+            Self::BodyQueryBackspace => app.pop_body_query_char(),
+            Self::BodyQueryConfirm => app.confirm_body_query(),
+            Self::BodyQueryCancel => app.cancel_body_query(),
+            Self::ClearBodyQuery => app.clear_body_query(),
+            Self::ToggleMark => app.toggle_mark(),
+            Self::OpenDiff => app.open_diff(),
+            Self::EnterReplay => app.enter_replay(),
+            Self::ReplayChar(c) => app.push_replay_char(*c),
+            Self::ReplayBackspace => app.pop_replay_char(),
+            Self::ReplayConfirm => app.confirm_replay(),
+            Self::ReplayCancel => app.cancel_replay(),
+            Self::EditReplay => {
+                app.pending_action = Some(app::PendingAction::EditReplay);
+            }
+            Self::ExportSanitized => app.export_sanitized(),
+            Self::ToggleDuplicatesOnly => app.toggle_duplicates_only(),
+            Self::EnterPreviewSearch => app.enter_preview_search(),
+            Self::PreviewSearchChar(c) => app.push_preview_search_char(*c),
+            Self::PreviewSearchBackspace => app.pop_preview_search_char(),
+            Self::PreviewSearchConfirm => app.confirm_preview_search(),
+            Self::PreviewSearchCancel => app.cancel_preview_search(),
+            Self::PreviewMatchNext => app.next_preview_match(),
+            Self::PreviewMatchPrev => app.prev_preview_match(),
+            Self::ClearPreviewSearch => app.clear_preview_search(),
             Self::EnterSearchMode => app.enter_search_mode(),
             Self::SearchChar(c) => app.push_search_char(*c),
             Self::SearchBackspace => app.pop_search_char(),
@@ -70,6 +170,29 @@ pub fn handle_key_events(key_event: KeyEvent, app: &app::App) -> Option<Command>
     if app.search_mode {
         return handle_search_key(key_event);
     }
+    if app.offset_input.is_some() {
+        return handle_offset_key(key_event);
+    }
+    if app.body_query_editing {
+        return handle_body_query_key(key_event);
+    }
+    if app.preview_search_input.is_some() {
+        return handle_preview_search_key(key_event);
+    }
+    if app.replay_input.is_some() {
+        return handle_replay_key(key_event);
+    }
+    if let Some(tree) = &app.json_tree {
+        if tree.find_input.is_some() {
+            return handle_tree_find_key(key_event);
+        }
+        // With the table focused, keys keep moving through entries and tabs.
+        if app.active_focus == app::ActiveFocus::Preview {
+            if let Some(command) = handle_tree_key(key_event) {
+                return Some(command);
+            }
+        }
+    }
 
     // Normal mode
     match key_event.code {
@@ -83,8 +206,12 @@ pub fn handle_key_events(key_event: KeyEvent, app: &app::App) -> Option<Command>
         }
         KeyCode::Char('/') => Some(Command::EnterSearchMode),
         KeyCode::Esc => {
-            if app.search_active {
+            if app.search_active || app.duplicates_only {
                 Some(Command::ClearSearch)
+            } else if app.preview_search.is_some() {
+                Some(Command::ClearPreviewSearch)
+            } else if !app.body_query.is_empty() {
+                Some(Command::ClearBodyQuery)
             } else {
                 None
             }
@@ -108,36 +235,146 @@ pub fn handle_key_events(key_event: KeyEvent, app: &app::App) -> Option<Command>
         KeyCode::Char('2') => Some(Command::SetTabBarState(app::TabBarState::Cookies)),
         KeyCode::Char('3') => Some(Command::SetTabBarState(app::TabBarState::Request)),
         KeyCode::Char('4') => Some(Command::SetTabBarState(app::TabBarState::Response)),
+        KeyCode::Char('5') => Some(Command::SetTabBarState(app::TabBarState::Inspect)),
+        KeyCode::Char('6') => Some(Command::SetTabBarState(app::TabBarState::Report)),
         KeyCode::Right => Some(Command::TabNext),
         KeyCode::Left => Some(Command::TabPrev),
         KeyCode::PageUp => Some(Command::PageUp),
         KeyCode::PageDown => Some(Command::PageDown),
         KeyCode::Char('h') => Some(Command::ToggleSyntaxHighlighting),
+        KeyCode::Char('x') => Some(Command::ToggleHexView),
+        KeyCode::Char(':') => Some(Command::EnterOffsetInput),
+        KeyCode::Char('t') => Some(Command::ToggleTreeView),
+        KeyCode::Char('|') => Some(Command::EnterBodyQuery),
+        KeyCode::Char('m') => Some(Command::ToggleMark),
+        KeyCode::Char('D') => Some(Command::OpenDiff),
+        KeyCode::Char('R') => Some(Command::EnterReplay),
+        KeyCode::Char('E') => Some(Command::EditReplay),
+        KeyCode::Char('S') => Some(Command::ExportSanitized),
+        KeyCode::Char('U') => Some(Command::ToggleDuplicatesOnly),
+        KeyCode::Char('!') => Some(Command::SetTabBarState(app::TabBarState::Secrets)),
+        KeyCode::Char('f') => Some(Command::EnterPreviewSearch),
+        KeyCode::Char('n') => Some(Command::PreviewMatchNext),
+        KeyCode::Char('N') => Some(Command::PreviewMatchPrev),
         KeyCode::Char('?') => Some(Command::SetTabBarState(app::TabBarState::Help)),
         _ => None,
     }
 }
 
 fn handle_search_key(key_event: KeyEvent) -> Option<Command> {
+    if key_event.code == KeyCode::Tab {
+        return Some(Command::SearchCycleScope);
+    }
+    handle_prompt_key(
+        key_event,
+        Command::SearchChar,
+        Command::SearchBackspace,
+        Command::SearchConfirm,
+        Command::SearchCancel,
+    )
+}
+
+fn handle_offset_key(key_event: KeyEvent) -> Option<Command> {
+    handle_prompt_key(
+        key_event,
+        Command::OffsetChar,
+        Command::OffsetBackspace,
+        Command::OffsetConfirm,
+        Command::OffsetCancel,
+    )
+}
+
+fn handle_body_query_key(key_event: KeyEvent) -> Option<Command> {
+    handle_prompt_key(
+        key_event,
+        Command::BodyQueryChar,
+        Command::BodyQueryBackspace,
+        Command::BodyQueryConfirm,
+        Command::BodyQueryCancel,
+    )
+}
+
+fn handle_preview_search_key(key_event: KeyEvent) -> Option<Command> {
+    handle_prompt_key(
+        key_event,
+        Command::PreviewSearchChar,
+        Command::PreviewSearchBackspace,
+        Command::PreviewSearchConfirm,
+        Command::PreviewSearchCancel,
+    )
+}
+
+fn handle_replay_key(key_event: KeyEvent) -> Option<Command> {
+    handle_prompt_key(
+        key_event,
+        Command::ReplayChar,
+        Command::ReplayBackspace,
+        Command::ReplayConfirm,
+        Command::ReplayCancel,
+    )
+}
+
+/// Line editing shared by every prompt. Ctrl+C still quits; other Ctrl and
+/// Alt chords are ignored rather than typed into the prompt.
+fn handle_prompt_key(
+    key_event: KeyEvent,
+    char: fn(char) -> Command,
+    backspace: Command,
+    confirm: Command,
+    cancel: Command,
+) -> Option<Command> {
     match key_event.code {
-        KeyCode::Enter => Some(Command::SearchConfirm),
-        KeyCode::Esc => Some(Command::SearchCancel),
-        KeyCode::Tab => Some(Command::SearchCycleScope),
-        KeyCode::Backspace => Some(Command::SearchBackspace),
-        KeyCode::Char(c) => {
-            // Pass through Ctrl+C as quit even in search mode
-            if key_event.modifiers == KeyModifiers::CONTROL && (c == 'c' || c == 'C') {
-                Some(Command::Quit)
-            } else if key_event.modifiers.is_empty() || key_event.modifiers == KeyModifiers::SHIFT {
-                Some(Command::SearchChar(c))
-            } else {
-                None
-            }
+        KeyCode::Enter => Some(confirm),
+        KeyCode::Esc => Some(cancel),
+        KeyCode::Backspace => Some(backspace),
+        KeyCode::Char('c') | KeyCode::Char('C') if key_event.modifiers == KeyModifiers::CONTROL => {
+            Some(Command::Quit)
+        }
+        KeyCode::Char(c) if !key_event.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+            Some(char(c))
         }
         _ => None,
     }
 }
 
+/// Keys captured while the JSON tree is open; anything else falls through to
+/// the normal bindings.
+fn handle_tree_key(key_event: KeyEvent) -> Option<Command> {
+    if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+        return None;
+    }
+    match key_event.code {
+        KeyCode::Char('j') | KeyCode::Down => Some(Command::TreeMove(1)),
+        KeyCode::Char('k') | KeyCode::Up => Some(Command::TreeMove(-1)),
+        KeyCode::PageDown => Some(Command::TreeMove(10)),
+        KeyCode::PageUp => Some(Command::TreeMove(-10)),
+        KeyCode::Char('g') => Some(Command::TreeFirst),
+        KeyCode::Char('G') => Some(Command::TreeLast),
+        KeyCode::Enter | KeyCode::Char(' ') => Some(Command::TreeToggle),
+        KeyCode::Char('l') | KeyCode::Right => Some(Command::TreeExpand),
+        KeyCode::Char('h') | KeyCode::Left => Some(Command::TreeCollapse),
+        KeyCode::Char(c @ '0'..='9') => {
+            Some(Command::TreeExpandToDepth(c.to_digit(10).unwrap_or(0) as usize))
+        }
+        KeyCode::Char('E') => Some(Command::TreeExpandAll),
+        KeyCode::Char('f') => Some(Command::TreeFindStart),
+        KeyCode::Char('n') => Some(Command::TreeFindNext),
+        KeyCode::Char('y') => Some(Command::TreeCopy),
+        KeyCode::Esc => Some(Command::ToggleTreeView),
+        _ => None,
+    }
+}
+
+fn handle_tree_find_key(key_event: KeyEvent) -> Option<Command> {
+    handle_prompt_key(
+        key_event,
+        Command::TreeFindChar,
+        Command::TreeFindBackspace,
+        Command::TreeFindConfirm,
+        Command::TreeFindCancel,
+    )
+}
+
 pub fn handle_mouse_events(app: &mut app::App, mouse_event: MouseEvent) -> Option<Command> {
     let split_y = app.window_size.height / 2;
 
@@ -165,20 +402,10 @@ pub fn handle_mouse_events(app: &mut app::App, mouse_event: MouseEvent) -> Optio
         }
         MouseEventKind::Down(crossterm::event::MouseButton::Left) => {
             if mouse_event.row >= split_y.saturating_sub(1) && mouse_event.row <= split_y + 1 {
-                let x = mouse_event.column;
-                if x < 15 {
-                    Some(Command::SetTabBarState(app::TabBarState::Headers))
-                } else if x < 30 {
-                    Some(Command::SetTabBarState(app::TabBarState::Cookies))
-                } else if x < 45 {
-                    Some(Command::SetTabBarState(app::TabBarState::Request))
-                } else if x < 61 {
-                    Some(Command::SetTabBarState(app::TabBarState::Response))
-                } else if x < 74 {
-                    Some(Command::SetTabBarState(app::TabBarState::Help))
-                } else {
-                    None
-                }
+                app.tab_zones()
+                    .into_iter()
+                    .find(|(zone, _)| zone.contains(&mouse_event.column))
+                    .map(|(_, tab)| Command::SetTabBarState(tab))
             } else if mouse_event.row < split_y {
                 let header_height = 2; // Border + Header row
                 if mouse_event.row >= header_height {
@@ -200,3 +427,97 @@ pub fn handle_mouse_events(app: &mut app::App, mouse_event: MouseEvent) -> Optio
         _ => None,
     }
 }
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prompts_ignore_control_and_alt_chords() {
+        let key = |modifiers| handle_preview_search_key(KeyEvent::new(KeyCode::Char('x'), modifiers));
+        assert!(matches!(key(KeyModifiers::NONE), Some(Command::PreviewSearchChar('x'))));
+        assert!(matches!(key(KeyModifiers::SHIFT), Some(Command::PreviewSearchChar('x'))));
+        assert!(key(KeyModifiers::CONTROL).is_none());
+        assert!(key(KeyModifiers::ALT).is_none());
+
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(matches!(handle_replay_key(ctrl_c), Some(Command::Quit)));
+        let tab = KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE);
+        assert!(matches!(handle_search_key(tab), Some(Command::SearchCycleScope)));
+    }
+
+    #[test]
+    fn tree_keys_reach_a_tree_opened_from_the_keyboard() {
+        let entry = crate::har::Entry::test("GET", "https://x.test/", 200)
+            .with_response_body("application/json", r#"{"a":{"b":1},"c":[1,2]}"#);
+        let mut app = app::App::init(crate::har::Har::new(vec![entry]));
+        app.window_size = ratatui::layout::Rect::new(0, 0, 160, 40);
+        let press = |app: &mut app::App, code| {
+            let command = handle_key_events(KeyEvent::new(code, KeyModifiers::NONE), app);
+            if let Some(command) = &command {
+                command.exec(app);
+            }
+            app.get_preview_text();
+            command
+        };
+
+        press(&mut app, KeyCode::Char('4'));
+        assert!(matches!(press(&mut app, KeyCode::Char('t')), Some(Command::ToggleTreeView)));
+        assert!(app.json_tree.is_some());
+        assert!(matches!(press(&mut app, KeyCode::Char('j')), Some(Command::TreeMove(1))));
+        assert!(matches!(press(&mut app, KeyCode::Char('l')), Some(Command::TreeExpand)));
+        assert!(matches!(press(&mut app, KeyCode::Char('y')), Some(Command::TreeCopy)));
+
+        // Closing the tree gives `j` back to the table.
+        assert!(matches!(press(&mut app, KeyCode::Esc), Some(Command::ToggleTreeView)));
+        assert!(app.json_tree.is_none());
+        assert!(matches!(press(&mut app, KeyCode::Char('j')), Some(Command::TableFocusDelta(1))));
+
+        // Clicking the table while the tree is open does the same.
+        press(&mut app, KeyCode::Char('t'));
+        app.active_focus = app::ActiveFocus::Table;
+        assert!(matches!(press(&mut app, KeyCode::Char('j')), Some(Command::TableFocusDelta(1))));
+    }
+
+    #[test]
+    fn tab_clicks_follow_the_rendered_labels() {
+        use ratatui::buffer::Buffer;
+        use ratatui::layout::Rect;
+        use ratatui::widgets::Widget;
+
+        let har = crate::har::Har::new(vec![crate::har::Entry::test("GET", "https://x.test/", 200)]);
+        let mut app = app::App::init(har);
+        app.window_size = Rect::new(0, 0, 160, 40);
+        let area = Rect::new(0, 20, 160, 20);
+        let mut buf = Buffer::empty(area);
+        crate::ui::PreviewWidget::init(&app).render(area, &mut buf);
+
+        let zones = app.tab_zones();
+        let tabs: Vec<_> = zones.iter().map(|(_, tab)| *tab).collect();
+        let mut expected = app::TabBarState::MAIN.to_vec();
+        expected.extend([app::TabBarState::Secrets, app::TabBarState::Help]);
+        assert_eq!(tabs, expected);
+        for (zone, tab) in &zones {
+            let text: String = zone.clone().map(|x| buf.get(x, 20).symbol()).collect();
+            assert_eq!(text.trim(), tab.to_string().trim());
+        }
+
+        let click = |app: &mut app::App, column| {
+            let event = MouseEvent {
+                kind: MouseEventKind::Down(crossterm::event::MouseButton::Left),
+                column,
+                row: 20,
+                modifiers: KeyModifiers::NONE,
+            };
+            match handle_mouse_events(app, event) {
+                Some(Command::SetTabBarState(tab)) => Some(tab),
+                _ => None,
+            }
+        };
+        assert_eq!(click(&mut app, 159), Some(app::TabBarState::Help));
+        assert_eq!(click(&mut app, 2), Some(app::TabBarState::Headers));
+        assert_eq!(click(&mut app, 124), None);
+    }
+}
@@ -1,8 +1,30 @@
-use serde::{Deserialize, Deserializer};
-use std::{fs, io::BufReader, path::Path};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fs,
+    io::{BufReader, BufWriter},
+    path::Path,
+};
 use url::Url;
 
 impl Har {
+    /// A HAR 1.2 log holding `entries`, with harview as its creator.
+    pub fn new(entries: Vec<Entry>) -> Self {
+        Self {
+            log: Log {
+                version: Some("1.2".to_string()),
+                creator: Some(Creator {
+                    name: Some("harview".to_string()),
+                    version: Some(env!("CARGO_PKG_VERSION").to_string()),
+                    extra: Default::default(),
+                }),
+                browser: None,
+                pages: None,
+                entries,
+                extra: Default::default(),
+            },
+        }
+    }
+
     pub async fn from_file(path: &Path) -> anyhow::Result<Self> {
         let file = fs::File::open(path)?;
         let reader = BufReader::new(file);
@@ -10,6 +32,74 @@ impl Har {
 
         Ok(har)
     }
+
+    pub fn to_file(&self, path: &Path) -> anyhow::Result<()> {
+        let file = fs::File::create(path)?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)?;
+
+        Ok(())
+    }
+
+    /// Like [`Har::to_file`] without indentation, for files that must stay small.
+    pub fn to_file_compact(&self, path: &Path) -> anyhow::Result<()> {
+        let file = fs::File::create(path)?;
+        serde_json::to_writer(BufWriter::new(file), self)?;
+
+        Ok(())
+    }
+}
+
+impl Request {
+    /// First header with the given name, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+}
+
+impl PostData {
+    /// Body bytes, base64-decoded when the text is marked as base64 by
+    /// [`BASE64_MARKER`]. Text that is not valid base64 is returned unchanged.
+    pub fn bytes(&self) -> Vec<u8> {
+        use base64::prelude::*;
+        if self.is_base64() {
+            if let Ok(bytes) = BASE64_STANDARD.decode(&self.text) {
+                return bytes;
+            }
+        }
+        self.text.as_bytes().to_vec()
+    }
+
+    /// Whether `text` holds a base64-encoded binary body. HAR has no
+    /// `encoding` field for request bodies, so the recorder marks them with
+    /// [`BASE64_MARKER`] the way `content.encoding` marks response bodies.
+    pub fn is_base64(&self) -> bool {
+        let (key, value) = BASE64_MARKER;
+        self.extra.get(key).and_then(|v| v.as_str()) == Some(value)
+    }
+}
+
+/// Custom `postData` field marking a base64-encoded request body.
+pub const BASE64_MARKER: (&str, &str) = ("_encoding", "base64");
+
+impl Response {
+    /// First header with the given name, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+}
+
+fn find_header<'a>(headers: &'a [Header], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|h| h.name.eq_ignore_ascii_case(name))
+        .map(|h| h.value.as_str())
+}
+
+fn serialize_url<S>(url: &Url, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(url.as_str())
 }
 
 fn deserialize_url<'de, D>(deserializer: D) -> Result<Url, D::Error>
@@ -20,53 +110,76 @@ where
     Url::parse(&s).map_err(serde::de::Error::custom)
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+/// Members a HAR object has beyond the ones modelled here, such as `comment`,
+/// cookie attributes or exporter extensions like `_initiator`. They are kept so
+/// that writing a capture back out loses nothing.
+pub type Extra = serde_json::Map<String, serde_json::Value>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Har {
     pub log: Log,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Log {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub creator: Option<Creator>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub browser: Option<Browser>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pages: Option<Vec<Page>>,
     pub entries: Vec<Entry>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Creator {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Browser {
     pub name: String,
     pub version: String,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Page {
     pub id: String,
     pub page_timings: PageTimings,
     pub started_date_time: String,
     pub title: String,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PageTimings {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub on_content_load: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub on_load: Option<f64>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Entry {
     pub started_date_time: String,
@@ -75,66 +188,92 @@ pub struct Entry {
     pub cache: Cache,
     pub timings: Timings,
     pub time: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "_securityState")]
     pub security_state: Option<String>,
+    /// Chrome's `memory` or `disk` when the response came from its cache.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "_fromCache")]
+    pub from_cache: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pageref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "serverIPAddress")]
     pub server_ipaddress: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub connection: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Request {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub body_size: Option<i64>,
     pub method: String,
-    #[serde(deserialize_with = "deserialize_url")]
+    #[serde(serialize_with = "serialize_url", deserialize_with = "deserialize_url")]
     pub url: url::Url,
     pub http_version: String,
     pub headers: Vec<Header>,
     pub cookies: Vec<Cookie>,
     pub query_string: Vec<QueryString>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub headers_size: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub post_data: Option<PostData>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Header {
     pub name: String,
     pub value: String,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Cookie {
     pub name: String,
     pub value: String,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryString {
     pub name: String,
     pub value: String,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PostData {
     pub mime_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub params: Option<Vec<Param>>,
     pub text: String,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Param {
     pub name: String,
     pub value: String,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     pub status: i64,
@@ -145,33 +284,200 @@ pub struct Response {
     pub content: Content,
     #[serde(rename = "redirectURL")]
     pub redirect_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub headers_size: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub body_size: Option<i64>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Content {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mime_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub size: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub encoding: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+/// The browser cache's copy of the resource before and after the request.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Cache {}
+pub struct Cache {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_request: Option<CacheEntry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_request: Option<CacheEntry>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+    #[serde(default)]
+    pub last_access: String,
+    #[serde(default, rename = "eTag")]
+    pub e_tag: String,
+    #[serde(default)]
+    pub hit_count: i64,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Timings {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub blocked: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dns: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub ssl: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub connect: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub send: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub wait: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub receive: Option<f64>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// ── Test fixtures ─────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+fn test_headers(pairs: &[(&str, &str)]) -> Vec<Header> {
+    pairs
+        .iter()
+        .map(|(name, value)| Header {
+            name: name.to_string(),
+            value: value.to_string(),
+            extra: Default::default(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+impl Entry {
+    /// Minimal entry for tests: `method url` answered with `status` and an
+    /// empty JSON body after 10 ms. Tests adjust the fields they care about,
+    /// directly or through the `with_*` helpers.
+    pub fn test(method: &str, url: &str, status: i64) -> Self {
+        Entry {
+            started_date_time: "2024-06-01T12:00:00.000Z".to_string(),
+            request: Request {
+                body_size: Some(0),
+                method: method.to_string(),
+                url: Url::parse(url).expect("valid test URL"),
+                http_version: "HTTP/1.1".to_string(),
+                headers: Vec::new(),
+                cookies: Vec::new(),
+                query_string: Vec::new(),
+                headers_size: Some(-1),
+                post_data: None,
+                extra: Default::default(),
+            },
+            response: Response {
+                status,
+                status_text: String::new(),
+                http_version: "HTTP/1.1".to_string(),
+                headers: Vec::new(),
+                cookies: Vec::new(),
+                content: Content {
+                    mime_type: Some("application/json".to_string()),
+                    size: Some(0),
+                    text: None,
+                    encoding: None,
+                    extra: Default::default(),
+                },
+                redirect_url: String::new(),
+                headers_size: Some(-1),
+                body_size: Some(0),
+                extra: Default::default(),
+            },
+            cache: Cache::default(),
+            timings: Timings {
+                blocked: None,
+                dns: None,
+                ssl: None,
+                connect: None,
+                send: Some(0.0),
+                wait: Some(10.0),
+                receive: Some(0.0),
+                extra: Default::default(),
+            },
+            time: 10.0,
+            security_state: None,
+            from_cache: None,
+            pageref: None,
+            server_ipaddress: None,
+            connection: None,
+            extra: Default::default(),
+        }
+    }
+
+    pub fn with_started(mut self, started: &str) -> Self {
+        self.started_date_time = started.to_string();
+        self
+    }
+
+    /// Total time, all of it spent waiting for the server.
+    pub fn with_time(mut self, ms: f64) -> Self {
+        self.time = ms;
+        self.timings.wait = Some(ms);
+        self
+    }
+
+    pub fn with_request_headers(mut self, pairs: &[(&str, &str)]) -> Self {
+        self.request.headers.extend(test_headers(pairs));
+        self
+    }
+
+    pub fn with_response_headers(mut self, pairs: &[(&str, &str)]) -> Self {
+        self.response.headers.extend(test_headers(pairs));
+        self
+    }
+
+    pub fn with_request_cookies(mut self, pairs: &[(&str, &str)]) -> Self {
+        self.request.cookies.extend(pairs.iter().map(|(name, value)| Cookie {
+            name: name.to_string(),
+            value: value.to_string(),
+            extra: Default::default(),
+        }));
+        self
+    }
+
+    pub fn with_request_body(mut self, mime_type: &str, text: &str) -> Self {
+        self.request.post_data = Some(PostData {
+            mime_type: mime_type.to_string(),
+            params: None,
+            text: text.to_string(),
+            extra: Default::default(),
+        });
+        self
+    }
+
+    /// Response body, with its size as both the content and transfer size.
+    pub fn with_response_body(mut self, mime_type: &str, text: &str) -> Self {
+        let content = &mut self.response.content;
+        content.mime_type = Some(mime_type.to_string());
+        content.size = Some(text.len() as i64);
+        content.text = Some(text.to_string());
+        self.response.body_size = content.size;
+        self
+    }
 }
 
 // ── Tests ─────────────────────────────────────────────────────────────────────
@@ -306,6 +612,39 @@ mod tests {
         assert!(result.is_err(), "invalid URL should fail to deserialize");
     }
 
+    #[test]
+    fn serialize_round_trip() {
+        let har: Har = serde_json::from_str(MINIMAL_HAR).unwrap();
+        let json = serde_json::to_value(&har).unwrap();
+        let entry = &json["log"]["entries"][0];
+        assert_eq!(entry["request"]["url"], "https://example.com/api/data?q=hello");
+        assert_eq!(entry["response"]["redirectURL"], "");
+        // Absent optional fields stay absent rather than becoming null.
+        assert!(entry["response"]["content"].get("encoding").is_none());
+        assert!(entry["timings"].get("blocked").is_none());
+        assert_eq!(serde_json::from_value::<Har>(json).unwrap(), har);
+    }
+
+    #[test]
+    fn unknown_members_survive_a_round_trip() {
+        let mut json: serde_json::Value = serde_json::from_str(MINIMAL_HAR).unwrap();
+        json["log"]["comment"] = "exported for review".into();
+        let entry = &mut json["log"]["entries"][0];
+        entry["_initiator"] = serde_json::json!({"type": "script", "url": "https://example.com/app.js"});
+        entry["_resourceType"] = "fetch".into();
+        entry["_priority"] = "High".into();
+        entry["request"]["cookies"] = serde_json::json!([{
+            "name": "sid", "value": "1", "path": "/", "domain": ".example.com",
+            "expires": "2025-01-01T00:00:00.000Z", "httpOnly": true, "secure": true
+        }]);
+        entry["response"]["content"]["compression"] = 12.into();
+        entry["timings"]["_blocked_queueing"] = 0.5.into();
+
+        let har: Har = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(har.log.entries[0].request.cookies[0].extra["httpOnly"], true);
+        assert_eq!(serde_json::to_value(&har).unwrap(), json);
+    }
+
     #[test]
     fn parse_empty_entries_list() {
         let json = r#"{
@@ -317,6 +656,15 @@ mod tests {
         assert_eq!(har.log.entries.len(), 0);
     }
 
+    #[test]
+    fn header_lookup_is_case_insensitive() {
+        let har: Har = serde_json::from_str(MINIMAL_HAR).unwrap();
+        let entry = &har.log.entries[0];
+        assert_eq!(entry.response.header("content-type"), Some("application/json"));
+        assert_eq!(entry.request.header("ACCEPT"), Some("application/json"));
+        assert_eq!(entry.response.header("Content-Encoding"), None);
+    }
+
     #[test]
     fn parse_base64_encoded_response_body() {
         use base64::prelude::*;
@@ -0,0 +1,381 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Write as _;
+
+use crate::app;
+use crate::entry_diff;
+use crate::har::{self, Har};
+
+/// Thresholds for reporting a matched request as a timing regression.
+#[derive(Debug, Clone, Copy)]
+pub struct DiffOptions {
+    /// Minimum slowdown relative to the baseline, in percent.
+    pub slower_pct: f64,
+    /// Minimum absolute slowdown, in milliseconds.
+    pub slower_ms: f64,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            slower_pct: 50.0,
+            slower_ms: 100.0,
+        }
+    }
+}
+
+/// URL with the parts that vary between otherwise identical requests
+/// normalised: lowercase host, no default port or fragment, sorted query.
+pub fn normalize_url(url: &url::Url) -> String {
+    let mut out = format!("{}://{}", url.scheme(), url.host_str().unwrap_or_default().to_lowercase());
+    if let Some(port) = url.port() {
+        let _ = write!(out, ":{}", port);
+    }
+    out.push_str(url.path());
+    let mut query: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+    if !query.is_empty() {
+        query.sort();
+        let joined: Vec<String> = query.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        out.push('?');
+        out.push_str(&joined.join("&"));
+    }
+    out
+}
+
+/// Method and normalised URL, used to pair requests across captures.
+pub fn endpoint(entry: &har::Entry) -> String {
+    format!(
+        "{} {}",
+        entry.request.method.to_uppercase(),
+        normalize_url(&entry.request.url)
+    )
+}
+
+/// For each entry of `current`, the index of its counterpart in `baseline`.
+/// Repeated calls to the same endpoint are paired in order of occurrence.
+pub fn match_entries(baseline: &Har, current: &Har) -> Vec<Option<usize>> {
+    let mut queues: HashMap<String, VecDeque<usize>> = HashMap::new();
+    for (i, entry) in baseline.log.entries.iter().enumerate() {
+        queues.entry(endpoint(entry)).or_default().push_back(i);
+    }
+    current
+        .log
+        .entries
+        .iter()
+        .map(|entry| queues.get_mut(&endpoint(entry)).and_then(|q| q.pop_front()))
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusChange {
+    pub endpoint: String,
+    pub before: i64,
+    pub after: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimingChange {
+    pub endpoint: String,
+    pub before_ms: f64,
+    pub after_ms: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BodyChange {
+    pub endpoint: String,
+    /// Changed lines of the normalised body; `None` for binary bodies.
+    pub changed_lines: Option<usize>,
+}
+
+/// Behavioural differences between a baseline capture and a current one.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Report {
+    pub baseline_count: usize,
+    pub current_count: usize,
+    pub matched: usize,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub status_changes: Vec<StatusChange>,
+    pub slower: Vec<TimingChange>,
+    pub body_changes: Vec<BodyChange>,
+}
+
+pub fn compare(baseline: &Har, current: &Har, options: DiffOptions) -> Report {
+    let matches = match_entries(baseline, current);
+    let baseline_endpoints: HashSet<String> = baseline.log.entries.iter().map(endpoint).collect();
+    let current_endpoints: HashSet<String> = current.log.entries.iter().map(endpoint).collect();
+
+    let mut report = Report {
+        baseline_count: baseline.log.entries.len(),
+        current_count: current.log.entries.len(),
+        matched: matches.iter().flatten().count(),
+        ..Report::default()
+    };
+    let mut seen = HashSet::new();
+    for entry in &current.log.entries {
+        let key = endpoint(entry);
+        if !baseline_endpoints.contains(&key) && seen.insert(key.clone()) {
+            report.added.push(key);
+        }
+    }
+    for entry in &baseline.log.entries {
+        let key = endpoint(entry);
+        if !current_endpoints.contains(&key) && seen.insert(key.clone()) {
+            report.removed.push(key);
+        }
+    }
+
+    for (after, before) in current.log.entries.iter().zip(&matches) {
+        let Some(before) = before.map(|i| &baseline.log.entries[i]) else {
+            continue;
+        };
+        let key = endpoint(after);
+        if before.response.status != after.response.status {
+            report.status_changes.push(StatusChange {
+                endpoint: key.clone(),
+                before: before.response.status,
+                after: after.response.status,
+            });
+        }
+        let slowdown = after.time - before.time;
+        if slowdown >= options.slower_ms && slowdown >= before.time * options.slower_pct / 100.0 {
+            report.slower.push(TimingChange {
+                endpoint: key.clone(),
+                before_ms: before.time,
+                after_ms: after.time,
+            });
+        }
+        if let Some(changed_lines) = body_change(before, after) {
+            report.body_changes.push(BodyChange {
+                endpoint: key,
+                changed_lines,
+            });
+        }
+    }
+    report
+}
+
+/// `None` when the response bodies are equivalent, otherwise the number of
+/// changed lines (`Some(None)` for binary bodies).
+fn body_change(before: &har::Entry, after: &har::Entry) -> Option<Option<usize>> {
+    let (old, new) = (app::response_text(before), app::response_text(after));
+    let normalized = |t: &Option<String>| t.as_deref().map(entry_diff::normalize_body);
+    if normalized(&old) == normalized(&new) {
+        return None;
+    }
+    let binary = [before, after].iter().any(|e| {
+        let mime = e.response.content.mime_type.as_deref().unwrap_or_default();
+        app::decode_response(e).is_some_and(|d| crate::hexdump::is_binary(mime, &d.bytes))
+    });
+    if binary {
+        return Some(None);
+    }
+    let rows = entry_diff::body_diff(old.as_deref(), new.as_deref());
+    Some(Some(rows.iter().filter(|r| r.is_change()).count()))
+}
+
+impl Report {
+    /// Whether the captures behave the same (no added/removed endpoints,
+    /// status changes, regressions or body differences).
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.status_changes.is_empty()
+            && self.slower.is_empty()
+            && self.body_changes.is_empty()
+    }
+
+    /// One-line overview, e.g. `2 added · 1 removed · 3 status · 1 slower · 4 bodies`.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} added · {} removed · {} status · {} slower · {} bodies",
+            self.added.len(),
+            self.removed.len(),
+            self.status_changes.len(),
+            self.slower.len(),
+            self.body_changes.len()
+        )
+    }
+
+    /// Plain-text report for the terminal.
+    pub fn render(&self, baseline_name: &str, current_name: &str, options: DiffOptions) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "Comparing {} ({} entries) → {} ({} entries)",
+            baseline_name, self.baseline_count, current_name, self.current_count
+        );
+        let _ = writeln!(out, "Matched {} requests by method and normalized URL", self.matched);
+
+        let mut section = |title: String, lines: Vec<String>| {
+            if !lines.is_empty() {
+                let _ = writeln!(out, "\n{} ({}):", title, lines.len());
+                for line in lines {
+                    let _ = writeln!(out, "  {}", line);
+                }
+            }
+        };
+        section(
+            "Added endpoints".to_string(),
+            self.added.iter().map(|e| format!("+ {}", e)).collect(),
+        );
+        section(
+            "Removed endpoints".to_string(),
+            self.removed.iter().map(|e| format!("- {}", e)).collect(),
+        );
+        section(
+            "Status changes".to_string(),
+            self.status_changes
+                .iter()
+                .map(|c| format!("{}  {} → {}", c.endpoint, c.before, c.after))
+                .collect(),
+        );
+        section(
+            format!(
+                "Timing regressions (≥ {:.0}% and ≥ {:.0} ms slower)",
+                options.slower_pct, options.slower_ms
+            ),
+            self.slower
+                .iter()
+                .map(|t| {
+                    format!(
+                        "{}  {:.0} ms → {:.0} ms (+{:.0}%)",
+                        t.endpoint,
+                        t.before_ms,
+                        t.after_ms,
+                        (t.after_ms - t.before_ms) / t.before_ms.max(1.0) * 100.0
+                    )
+                })
+                .collect(),
+        );
+        section(
+            "Body differences".to_string(),
+            self.body_changes
+                .iter()
+                .map(|b| match b.changed_lines {
+                    Some(1) => format!("{}  (1 line changed)", b.endpoint),
+                    Some(n) => format!("{}  ({} lines changed)", b.endpoint, n),
+                    None => format!("{}  (binary)", b.endpoint),
+                })
+                .collect(),
+        );
+        if self.is_empty() {
+            out.push_str("\nNo differences.\n");
+        }
+        out
+    }
+}
+
+/// Baseline capture loaded next to the viewed one (`harview diff --tui`), so
+/// the Diff tab can compare each entry with its counterpart.
+#[derive(Debug)]
+pub struct Baseline {
+    pub name: String,
+    pub har: Har,
+    /// For each viewed entry, the index of its counterpart in `har`.
+    pub matches: Vec<Option<usize>>,
+    pub summary: String,
+}
+
+impl Baseline {
+    pub fn new(name: String, har: Har, current: &Har, options: DiffOptions) -> Self {
+        let matches = match_entries(&har, current);
+        let summary = compare(&har, current, options).summary();
+        Self {
+            name,
+            har,
+            matches,
+            summary,
+        }
+    }
+
+    /// Counterpart of entry `index` of the viewed capture.
+    pub fn counterpart(&self, index: usize) -> Option<&har::Entry> {
+        self.matches
+            .get(index)
+            .copied()
+            .flatten()
+            .map(|i| &self.har.log.entries[i])
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(method: &str, url: &str, status: i64, time: f64, body: &str) -> har::Entry {
+        har::Entry::test(method, url, status)
+            .with_time(time)
+            .with_response_body("application/json", body)
+    }
+
+    #[test]
+    fn urls_are_normalized() {
+        let url = url::Url::parse("https://API.example.com:443/v1/items?b=2&a=1#frag").unwrap();
+        assert_eq!(normalize_url(&url), "https://api.example.com/v1/items?a=1&b=2");
+        let url = url::Url::parse("http://localhost:8080/x").unwrap();
+        assert_eq!(normalize_url(&url), "http://localhost:8080/x");
+    }
+
+    #[test]
+    fn repeated_endpoints_pair_in_order() {
+        let a = Har::new(vec![
+            entry("GET", "https://x.test/a", 200, 10.0, "1"),
+            entry("GET", "https://x.test/a", 200, 10.0, "2"),
+        ]);
+        let b = Har::new(vec![
+            entry("POST", "https://x.test/a", 200, 10.0, "1"),
+            entry("get", "https://x.test/a", 200, 10.0, "1"),
+            entry("GET", "https://x.test/a", 200, 10.0, "2"),
+            entry("GET", "https://x.test/a", 200, 10.0, "3"),
+        ]);
+        assert_eq!(match_entries(&a, &b), vec![None, Some(0), Some(1), None]);
+    }
+
+    #[test]
+    fn report_finds_each_kind_of_change() {
+        let a = Har::new(vec![
+            entry("GET", "https://x.test/same", 200, 100.0, r#"{"a":1,"b":2}"#),
+            entry("GET", "https://x.test/status", 200, 100.0, "{}"),
+            entry("GET", "https://x.test/slow", 200, 100.0, "{}"),
+            entry("GET", "https://x.test/body", 200, 100.0, r#"{"v":1}"#),
+            entry("GET", "https://x.test/gone", 200, 100.0, "{}"),
+        ]);
+        let b = Har::new(vec![
+            entry("GET", "https://x.test/same", 200, 120.0, r#"{"b":2,"a":1}"#),
+            entry("GET", "https://x.test/status", 500, 100.0, "{}"),
+            entry("GET", "https://x.test/slow", 200, 400.0, "{}"),
+            entry("GET", "https://x.test/body", 200, 100.0, r#"{"v":2}"#),
+            entry("GET", "https://x.test/new", 200, 100.0, "{}"),
+        ]);
+        let report = compare(&a, &b, DiffOptions::default());
+        assert_eq!(report.matched, 4);
+        assert_eq!(report.added, vec!["GET https://x.test/new"]);
+        assert_eq!(report.removed, vec!["GET https://x.test/gone"]);
+        assert_eq!(report.status_changes.len(), 1);
+        assert_eq!(report.status_changes[0].after, 500);
+        assert_eq!(report.slower.len(), 1);
+        assert_eq!(report.slower[0].endpoint, "GET https://x.test/slow");
+        assert_eq!(
+            report.body_changes,
+            vec![BodyChange {
+                endpoint: "GET https://x.test/body".to_string(),
+                changed_lines: Some(1)
+            }]
+        );
+        assert_eq!(report.summary(), "1 added · 1 removed · 1 status · 1 slower · 1 bodies");
+
+        let text = report.render("a.har", "b.har", DiffOptions::default());
+        assert!(text.contains("Status changes (1):\n  GET https://x.test/status  200 → 500"));
+        assert!(text.contains("100 ms → 400 ms (+300%)"));
+    }
+
+    #[test]
+    fn identical_captures_report_no_differences() {
+        let a = Har::new(vec![entry("GET", "https://x.test/a", 200, 10.0, "{}")]);
+        let report = compare(&a, &a, DiffOptions::default());
+        assert!(report.is_empty());
+        assert!(report.render("a", "b", DiffOptions::default()).ends_with("No differences.\n"));
+    }
+}
@@ -0,0 +1,402 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::har::{self, Har};
+
+/// Start time of an entry or page, for ordering; unparsable timestamps sort first.
+fn started(started_date_time: &str) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+    chrono::DateTime::parse_from_rfc3339(started_date_time).ok()
+}
+
+/// Entries of several captures in one timeline sorted by start time, with the
+/// index of the capture each entry came from. Entries starting at the same
+/// instant keep their file order.
+///
+/// Pages with the same start time and title are the same page exported twice
+/// and kept once; other pages whose ids collide are renamed, and the `pageref`
+/// of their entries follows.
+pub fn timeline(captures: Vec<Har>) -> (Har, Vec<usize>) {
+    let mut tagged: Vec<(usize, har::Entry)> = Vec::new();
+    let mut pages: Vec<har::Page> = Vec::new();
+    for (source, capture) in captures.into_iter().enumerate() {
+        let mut ids = HashMap::new();
+        for page in capture.log.pages.unwrap_or_default() {
+            let same = pages
+                .iter()
+                .find(|p| p.started_date_time == page.started_date_time && p.title == page.title);
+            let id = match same {
+                Some(existing) => existing.id.clone(),
+                None => {
+                    let id = unique(&page.id, |id| pages.iter().any(|p| p.id == id));
+                    pages.push(har::Page { id: id.clone(), ..page.clone() });
+                    id
+                }
+            };
+            ids.insert(page.id, id);
+        }
+        tagged.extend(capture.log.entries.into_iter().map(|mut entry| {
+            if let Some(id) = entry.pageref.as_ref().and_then(|r| ids.get(r)) {
+                entry.pageref = Some(id.clone());
+            }
+            (source, entry)
+        }));
+    }
+    tagged.sort_by_key(|(_, entry)| started(&entry.started_date_time));
+    pages.sort_by_key(|page| started(&page.started_date_time));
+    let (sources, entries): (Vec<usize>, Vec<har::Entry>) = tagged.into_iter().unzip();
+
+    let mut har = Har::new(entries);
+    har.log.pages = (!pages.is_empty()).then_some(pages);
+    (har, sources)
+}
+
+/// Several captures as one HAR, for `harview merge`. The creator, browser and
+/// any other log members are kept when all captures agree on them; otherwise
+/// harview is the creator and the rest is left out.
+pub fn merge(captures: Vec<Har>) -> Har {
+    let creator = agreed(captures.iter().map(|c| &c.log.creator));
+    let browser = agreed(captures.iter().map(|c| &c.log.browser));
+    let extra = captures.first().map(|c| c.log.extra.clone()).filter(|extra| captures.iter().all(|c| &c.log.extra == extra));
+    let (mut har, _) = timeline(captures);
+    if let Some(creator) = creator {
+        har.log.creator = Some(creator);
+    }
+    har.log.browser = browser;
+    har.log.extra = extra.unwrap_or_default();
+    har
+}
+
+/// The value all items share, if any.
+fn agreed<'a, T: Clone + PartialEq + 'a>(mut items: impl Iterator<Item = &'a Option<T>>) -> Option<T> {
+    let first = items.next()?.clone()?;
+    items.all(|item| item.as_ref() == Some(&first)).then_some(first)
+}
+
+/// `name`, or `name-2`, `name-3`, … when `taken` says it is in use.
+fn unique(name: &str, taken: impl Fn(&str) -> bool) -> String {
+    if !taken(name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|n| format!("{}-{}", name, n))
+        .find(|candidate| !taken(candidate))
+        .expect("unbounded range")
+}
+
+/// Short labels for files shown side by side: the file name, or the path as
+/// given when two files share a name.
+pub fn source_names(paths: &[impl AsRef<Path>]) -> Vec<String> {
+    let file_name = |p: &Path| {
+        p.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| p.display().to_string())
+    };
+    paths
+        .iter()
+        .map(|p| {
+            let p = p.as_ref();
+            let name = file_name(p);
+            let shared = paths
+                .iter()
+                .filter(|other| file_name(other.as_ref()) == name)
+                .count()
+                > 1;
+            if shared {
+                p.display().to_string()
+            } else {
+                name
+            }
+        })
+        .collect()
+}
+
+/// How `harview split` divides a capture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SplitBy {
+    /// One file per page (`pageref`)
+    Page,
+    /// One file per request host
+    Host,
+    /// Consecutive parts of at most `--max-mb` each
+    Size,
+}
+
+/// Divide `har` into labelled parts, in order of first appearance. Each part
+/// keeps the creator and browser of the original and the pages its entries
+/// refer to. With [`SplitBy::Size`], parts serialize (compactly) to at most
+/// `max_bytes` unless a single entry is larger than that.
+pub fn split(har: &Har, by: SplitBy, max_bytes: usize) -> Vec<(String, Har)> {
+    let groups: Vec<(String, Vec<&har::Entry>)> = match by {
+        SplitBy::Page => group_by(&har.log.entries, |e| e.pageref.clone().unwrap_or_else(|| "no-page".to_string())),
+        SplitBy::Host => group_by(&har.log.entries, |e| match (e.request.url.host_str(), e.request.url.port()) {
+            (Some(host), Some(port)) => format!("{}_{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => "no-host".to_string(),
+        }),
+        SplitBy::Size => {
+            let overhead = compact_size(&part(har, Vec::new(), true));
+            let mut chunks: Vec<Vec<&har::Entry>> = Vec::new();
+            let mut used = overhead;
+            for entry in &har.log.entries {
+                // Entry plus the comma separating it from the previous one.
+                let size = compact_size(entry) + 1;
+                match chunks.last_mut() {
+                    Some(chunk) if used + size <= max_bytes => chunk.push(entry),
+                    _ => {
+                        chunks.push(vec![entry]);
+                        used = overhead;
+                    }
+                }
+                used += size;
+            }
+            let width = chunks.len().to_string().len();
+            chunks
+                .into_iter()
+                .enumerate()
+                .map(|(i, chunk)| (format!("part-{:0width$}", i + 1, width = width), chunk))
+                .collect()
+        }
+    };
+
+    let mut labels: Vec<String> = Vec::new();
+    groups
+        .into_iter()
+        .map(|(label, entries)| {
+            let label = unique(&file_safe(&label), |l| labels.iter().any(|taken| taken == l));
+            labels.push(label.clone());
+            (label, part(har, entries.into_iter().cloned().collect(), false))
+        })
+        .collect()
+}
+
+fn group_by(entries: &[har::Entry], key: impl Fn(&har::Entry) -> String) -> Vec<(String, Vec<&har::Entry>)> {
+    let mut groups: Vec<(String, Vec<&har::Entry>)> = Vec::new();
+    for entry in entries {
+        let key = key(entry);
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, group)) => group.push(entry),
+            None => groups.push((key, vec![entry])),
+        }
+    }
+    groups
+}
+
+/// A copy of `har` holding `entries` and the pages they refer to (or every
+/// page, to measure the worst case).
+fn part(har: &Har, entries: Vec<har::Entry>, all_pages: bool) -> Har {
+    let pages = har.log.pages.as_ref().map(|pages| {
+        pages
+            .iter()
+            .filter(|p| all_pages || entries.iter().any(|e| e.pageref.as_deref() == Some(p.id.as_str())))
+            .cloned()
+            .collect::<Vec<_>>()
+    });
+    Har {
+        log: har::Log {
+            version: har.log.version.clone(),
+            creator: har.log.creator.clone(),
+            browser: har.log.browser.clone(),
+            pages: pages.filter(|p| !p.is_empty()),
+            entries,
+            extra: har.log.extra.clone(),
+        },
+    }
+}
+
+fn compact_size<T: serde::Serialize>(value: &T) -> usize {
+    serde_json::to_vec(value).map(|v| v.len()).unwrap_or(0)
+}
+
+/// `label` with anything but ASCII letters, digits, `.`, `-` and `_` replaced,
+/// for use in a file name.
+fn file_safe(label: &str) -> String {
+    let safe: String = label
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '_' })
+        .collect();
+    safe.trim_start_matches('.').to_string()
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(started: &str, path: &str) -> har::Entry {
+        har::Entry::test("GET", &format!("https://x.test{}", path), 200).with_started(started)
+    }
+
+    fn page(id: &str, started: &str, title: &str) -> har::Page {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "startedDateTime": started, "title": title, "pageTimings": {}
+        }))
+        .unwrap()
+    }
+
+    fn on_page(mut entry: har::Entry, pageref: &str) -> har::Entry {
+        entry.pageref = Some(pageref.to_string());
+        entry
+    }
+
+    fn creator(name: &str) -> Option<har::Creator> {
+        Some(har::Creator {
+            name: Some(name.to_string()),
+            version: Some("1.0".to_string()),
+            extra: Default::default(),
+        })
+    }
+
+    fn paths(har: &Har) -> Vec<&str> {
+        har.log.entries.iter().map(|e| e.request.url.path()).collect()
+    }
+
+    #[test]
+    fn timeline_interleaves_by_start_time() {
+        let phone = Har::new(vec![
+            entry("2024-06-01T12:00:01.000Z", "/phone-1"),
+            entry("2024-06-01T12:00:03.000Z", "/phone-2"),
+        ]);
+        // Same instants expressed in another offset.
+        let laptop = Har::new(vec![
+            entry("2024-06-01T14:00:02.000+02:00", "/laptop-1"),
+            entry("2024-06-01T14:00:03.000+02:00", "/laptop-2"),
+        ]);
+        let (har, sources) = timeline(vec![phone, laptop]);
+        assert_eq!(paths(&har), vec!["/phone-1", "/laptop-1", "/phone-2", "/laptop-2"]);
+        assert_eq!(sources, vec![0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn source_names_disambiguate_shared_file_names() {
+        assert_eq!(
+            source_names(&["a/session.har", "b/session.har", "c/other.har"]),
+            vec!["a/session.har", "b/session.har", "other.har"]
+        );
+    }
+
+    #[test]
+    fn merge_dedupes_pages_and_renames_colliding_ids() {
+        let mut first = Har::new(vec![
+            on_page(entry("2024-06-01T12:00:01.000Z", "/home"), "page_1"),
+            on_page(entry("2024-06-01T12:00:05.000Z", "/cart"), "page_2"),
+        ]);
+        first.log.pages = Some(vec![
+            page("page_1", "2024-06-01T12:00:00.000Z", "Home"),
+            page("page_2", "2024-06-01T12:00:04.000Z", "Cart"),
+        ]);
+        first.log.creator = creator("Firefox");
+        // Overlapping export: `page_1` is the same page, `page_2` is not.
+        let mut second = Har::new(vec![
+            on_page(entry("2024-06-01T12:00:02.000Z", "/home.css"), "page_1"),
+            on_page(entry("2024-06-01T12:00:09.000Z", "/checkout"), "page_2"),
+        ]);
+        second.log.pages = Some(vec![
+            page("page_1", "2024-06-01T12:00:00.000Z", "Home"),
+            page("page_2", "2024-06-01T12:00:08.000Z", "Checkout"),
+        ]);
+        second.log.creator = creator("Firefox");
+
+        let merged = merge(vec![first.clone(), second.clone()]);
+        let ids: Vec<_> = merged.log.pages.as_ref().unwrap().iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["page_1", "page_2", "page_2-2"]);
+        let refs: Vec<_> = merged.log.entries.iter().map(|e| e.pageref.as_deref().unwrap()).collect();
+        assert_eq!(refs, vec!["page_1", "page_1", "page_2", "page_2-2"]);
+        assert_eq!(paths(&merged), vec!["/home", "/home.css", "/cart", "/checkout"]);
+        assert_eq!(merged.log.creator, creator("Firefox"));
+
+        second.log.creator = creator("Chrome");
+        let merged = merge(vec![first, second]);
+        assert_eq!(merged.log.creator.unwrap().name.as_deref(), Some("harview"));
+    }
+
+    #[test]
+    fn merge_keeps_members_it_does_not_model() {
+        let exported = |started: &str, path: &str| {
+            let mut value = serde_json::to_value(entry(started, path)).unwrap();
+            value["_resourceType"] = serde_json::json!("fetch");
+            value["_initiator"] = serde_json::json!({ "type": "script", "lineNumber": 42 });
+            value["request"]["cookies"] = serde_json::json!([{
+                "name": "sid", "value": "abc", "path": "/", "domain": ".x.test",
+                "expires": "2024-07-01T00:00:00.000Z", "httpOnly": true, "secure": true,
+                "sameSite": "Lax"
+            }]);
+            value["timings"]["_blocked_queueing"] = serde_json::json!(0.25);
+            value
+        };
+        let originals = [
+            exported("2024-06-01T12:00:01.000Z", "/a"),
+            exported("2024-06-01T12:00:02.000Z", "/b"),
+        ];
+        let capture = |value: &serde_json::Value| {
+            let mut har = Har::new(vec![serde_json::from_value(value.clone()).unwrap()]);
+            har.log.extra.insert("comment".to_string(), serde_json::json!("exported twice"));
+            har
+        };
+
+        let merged = merge(originals.iter().map(capture).collect());
+        assert_eq!(merged.log.extra["comment"], "exported twice");
+        for (entry, original) in merged.log.entries.iter().zip(&originals) {
+            let written = serde_json::to_value(entry).unwrap();
+            assert_eq!(written, *original);
+            for pointer in ["/_resourceType", "/_initiator", "/request/cookies", "/timings/_blocked_queueing"] {
+                assert_eq!(
+                    serde_json::to_string(&written.pointer(pointer)).unwrap(),
+                    serde_json::to_string(&original.pointer(pointer)).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn split_by_page_and_host() {
+        let mut har = Har::new(vec![
+            on_page(entry("2024-06-01T12:00:01.000Z", "/a"), "page_1"),
+            entry("2024-06-01T12:00:02.000Z", "/b"),
+            on_page(entry("2024-06-01T12:00:03.000Z", "/c"), "page_2"),
+            on_page(entry("2024-06-01T12:00:04.000Z", "/d"), "page_1"),
+        ]);
+        har.log.entries[1].request.url = url::Url::parse("http://localhost:3000/b").unwrap();
+        har.log.pages = Some(vec![
+            page("page_1", "2024-06-01T12:00:00.000Z", "One"),
+            page("page_2", "2024-06-01T12:00:03.000Z", "Two"),
+        ]);
+
+        let parts = split(&har, SplitBy::Page, 0);
+        let labels: Vec<_> = parts.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(labels, vec!["page_1", "no-page", "page_2"]);
+        assert_eq!(paths(&parts[0].1), vec!["/a", "/d"]);
+        let pages = parts[0].1.log.pages.as_ref().unwrap();
+        assert_eq!((pages.len(), pages[0].id.as_str()), (1, "page_1"));
+        assert!(parts[1].1.log.pages.is_none());
+
+        let parts = split(&har, SplitBy::Host, 0);
+        let labels: Vec<_> = parts.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(labels, vec!["x.test", "localhost_3000"]);
+        assert_eq!(parts[0].1.log.entries.len(), 3);
+    }
+
+    #[test]
+    fn split_by_size_stays_under_the_limit() {
+        let entries: Vec<_> = (0..10)
+            .map(|i| {
+                let mut e = entry("2024-06-01T12:00:01.000Z", &format!("/{}", i));
+                e.response.content.text = Some("x".repeat(1000));
+                e
+            })
+            .collect();
+        let har = Har::new(entries);
+        let limit = 3 * compact_size(&har.log.entries[0]) + 500;
+
+        let parts = split(&har, SplitBy::Size, limit);
+        let labels: Vec<_> = parts.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(labels, vec!["part-1", "part-2", "part-3", "part-4"]);
+        assert!(parts.iter().all(|(_, part)| compact_size(part) <= limit));
+        let total: usize = parts.iter().map(|(_, part)| part.log.entries.len()).sum();
+        assert_eq!(total, 10);
+        assert_eq!(paths(&parts[3].1), vec!["/9"]);
+
+        // An entry over the limit still gets a part of its own.
+        assert_eq!(split(&har, SplitBy::Size, 10).len(), 10);
+    }
+}
@@ -0,0 +1,198 @@
+use ratatui::prelude::*;
+
+/// Number of bytes shown on each hex dump row.
+pub const BYTES_PER_ROW: usize = 16;
+
+/// How many leading bytes are inspected when sniffing for binary content.
+const SNIFF_LEN: usize = 8192;
+
+/// MIME types whose bodies are never meaningful as text.
+const BINARY_MIME_PREFIXES: &[&str] = &[
+    "image/",
+    "audio/",
+    "video/",
+    "font/",
+    "application/octet-stream",
+    "application/wasm",
+    "application/pdf",
+    "application/zip",
+    "application/gzip",
+    "application/x-protobuf",
+    "application/protobuf",
+    "application/vnd.google.protobuf",
+    "application/grpc",
+    "application/font-",
+    "application/x-font-",
+];
+
+/// Decide whether a body should be treated as binary, using the MIME type first
+/// and falling back to inspecting the bytes themselves.
+pub fn is_binary(mime_type: &str, bytes: &[u8]) -> bool {
+    let mime = mime_type.trim().to_lowercase();
+    // SVG is an image type but plain XML underneath.
+    if mime.starts_with("image/svg") {
+        return false;
+    }
+    if BINARY_MIME_PREFIXES.iter().any(|p| mime.starts_with(p)) {
+        return true;
+    }
+    looks_binary(bytes)
+}
+
+/// Byte-level heuristic: NUL bytes, invalid UTF-8, or a high share of control
+/// characters in the sniffed prefix mean the content is not text.
+pub fn looks_binary(bytes: &[u8]) -> bool {
+    let sample = &bytes[..bytes.len().min(SNIFF_LEN)];
+    if sample.is_empty() {
+        return false;
+    }
+    if sample.contains(&0) {
+        return true;
+    }
+    if let Err(e) = std::str::from_utf8(sample) {
+        // A multi-byte sequence cut off by the sample boundary is still text.
+        if e.error_len().is_some() {
+            return true;
+        }
+    }
+    let control = sample
+        .iter()
+        .filter(|&&b| b < 0x20 && !matches!(b, b'\n' | b'\r' | b'\t' | 0x0c))
+        .count();
+    control * 10 > sample.len()
+}
+
+/// Total number of rows needed to dump `len` bytes.
+pub fn row_count(len: usize) -> usize {
+    len.div_ceil(BYTES_PER_ROW)
+}
+
+/// Render `rows` rows of the dump starting at row `first_row`.
+pub fn render_rows(bytes: &[u8], first_row: usize, rows: usize) -> Vec<Line<'static>> {
+    bytes
+        .chunks(BYTES_PER_ROW)
+        .enumerate()
+        .skip(first_row)
+        .take(rows)
+        .map(|(row, chunk)| render_row(row * BYTES_PER_ROW, chunk))
+        .collect()
+}
+
+/// Full dump of `bytes` as plain text, one row per line.
+pub fn dump(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for (row, chunk) in bytes.chunks(BYTES_PER_ROW).enumerate() {
+        out.push_str(&format_row(row * BYTES_PER_ROW, chunk));
+        out.push('\n');
+    }
+    out
+}
+
+/// Format one row as `offset  hex bytes  |ascii|`, in the style of `hexdump -C`.
+pub fn format_row(offset: usize, chunk: &[u8]) -> String {
+    let (hex, ascii) = row_columns(chunk);
+    format!("{:08x}  {}  |{}|", offset, hex, ascii)
+}
+
+fn render_row(offset: usize, chunk: &[u8]) -> Line<'static> {
+    let (hex, ascii) = row_columns(chunk);
+    Line::from(vec![
+        Span::styled(format!("{:08x}", offset), Style::default().fg(Color::DarkGray)),
+        Span::raw("  "),
+        Span::styled(hex, Style::default().fg(Color::LightCyan)),
+        Span::raw("  "),
+        Span::styled(format!("|{}|", ascii), Style::default().fg(Color::Yellow)),
+    ])
+}
+
+fn row_columns(chunk: &[u8]) -> (String, String) {
+    let mut hex = String::with_capacity(BYTES_PER_ROW * 3 + 1);
+    for i in 0..BYTES_PER_ROW {
+        if i == BYTES_PER_ROW / 2 {
+            hex.push(' ');
+        }
+        match chunk.get(i) {
+            Some(b) => hex.push_str(&format!("{:02x}", b)),
+            None => hex.push_str("  "),
+        }
+        if i + 1 < BYTES_PER_ROW {
+            hex.push(' ');
+        }
+    }
+    let ascii = chunk
+        .iter()
+        .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' })
+        .collect();
+    (hex, ascii)
+}
+
+/// Parse a user-entered offset: `0x`-prefixed hex, or decimal otherwise.
+pub fn parse_offset(input: &str) -> Option<usize> {
+    let s = input.trim();
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        usize::from_str_radix(hex, 16).ok()
+    } else {
+        s.parse().ok()
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_by_mime_type() {
+        assert!(is_binary("image/png", b"plain"));
+        assert!(is_binary("font/woff2", b"plain"));
+        assert!(is_binary("application/wasm", b"plain"));
+        assert!(!is_binary("image/svg+xml", b"<svg/>"));
+        assert!(!is_binary("application/json", b"{\"a\":1}"));
+    }
+
+    #[test]
+    fn binary_by_content_sniffing() {
+        assert!(looks_binary(&[0x89, b'P', b'N', b'G', 0x00, 0x01]));
+        assert!(looks_binary(&[0xff, 0xfe, 0xfd, 0x41]));
+        assert!(!looks_binary("héllo wörld\n".as_bytes()));
+        assert!(!looks_binary(b""));
+    }
+
+    #[test]
+    fn truncated_utf8_at_sniff_boundary_is_text() {
+        let mut bytes = vec![b'a'; SNIFF_LEN - 1];
+        bytes.extend_from_slice("é".as_bytes());
+        assert!(!looks_binary(&bytes));
+    }
+
+    #[test]
+    fn format_full_and_partial_rows() {
+        let row = format_row(0, b"Hello, world!!!\n");
+        assert_eq!(
+            row,
+            "00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 21 21 0a  |Hello, world!!!.|"
+        );
+        let partial = format_row(0x10, &[0x00, 0x41]);
+        assert!(partial.starts_with("00000010  00 41    "));
+        assert!(partial.ends_with("|.A|"));
+        assert_eq!(partial.len(), row.len() - 14);
+    }
+
+    #[test]
+    fn render_rows_windowing() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(row_count(bytes.len()), 16);
+        assert_eq!(row_count(17), 2);
+        let lines = render_rows(&bytes, 15, 10);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].spans[0].content, "000000f0");
+    }
+
+    #[test]
+    fn parse_offset_hex_and_decimal() {
+        assert_eq!(parse_offset("0x1f"), Some(31));
+        assert_eq!(parse_offset(" 256 "), Some(256));
+        assert_eq!(parse_offset("zz"), None);
+    }
+}
@@ -0,0 +1,376 @@
+use base64::prelude::*;
+use image::{imageops::FilterType, ImageFormat, RgbaImage};
+use ratatui::prelude::*;
+use std::io::Cursor;
+
+/// Assumed terminal cell size in pixels, used to keep the aspect ratio when an
+/// image is scaled to a box of cells.
+const CELL_WIDTH_PX: u32 = 8;
+const CELL_HEIGHT_PX: u32 = 16;
+
+/// Kitty graphics payloads are sent in chunks of at most this many bytes.
+const KITTY_CHUNK: usize = 4096;
+
+/// How images are drawn in the Response tab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ImageProtocol {
+    /// Pick a protocol from the terminal's environment.
+    Auto,
+    Kitty,
+    Iterm2,
+    Sixel,
+    /// Unicode half-block characters; works in any true-color terminal.
+    Halfblocks,
+}
+
+impl ImageProtocol {
+    /// Resolve `Auto` from the environment variables terminals export.
+    pub fn detect(self) -> Self {
+        if self != Self::Auto {
+            return self;
+        }
+        let var = |name: &str| std::env::var(name).unwrap_or_default();
+        let term = var("TERM");
+        let term_program = var("TERM_PROGRAM");
+
+        if std::env::var_os("KITTY_WINDOW_ID").is_some()
+            || term.contains("kitty")
+            || term.contains("ghostty")
+            || term_program == "ghostty"
+        {
+            Self::Kitty
+        } else if term_program == "iTerm.app" || term_program == "WezTerm" {
+            Self::Iterm2
+        } else if term.contains("sixel")
+            || term.starts_with("foot")
+            || term_program == "mintty"
+            || std::env::var_os("MLTERM").is_some()
+        {
+            Self::Sixel
+        } else {
+            Self::Halfblocks
+        }
+    }
+
+    /// Whether the protocol draws outside ratatui's cell buffer.
+    pub fn is_graphics(self) -> bool {
+        matches!(self, Self::Kitty | Self::Iterm2 | Self::Sixel)
+    }
+}
+
+/// A decoded image body plus the metadata shown above the preview.
+#[derive(Debug, Clone)]
+pub struct ImagePreview {
+    pub format: &'static str,
+    pub width: u32,
+    pub height: u32,
+    pub byte_size: usize,
+    pub image: RgbaImage,
+}
+
+/// MIME types that get an image preview instead of a hex dump.
+pub fn is_image_mime(mime_type: &str) -> bool {
+    let mime = mime_type.trim().to_lowercase();
+    ["image/png", "image/jpeg", "image/jpg", "image/gif", "image/webp", "image/svg"]
+        .iter()
+        .any(|m| mime.starts_with(m))
+}
+
+/// Decode an image body. The format is sniffed from the bytes, falling back to
+/// the MIME type for SVG, which has no magic number.
+pub fn decode(mime_type: &str, bytes: &[u8]) -> Option<ImagePreview> {
+    let sniffed = image::guess_format(bytes).ok();
+    match sniffed {
+        Some(format @ (ImageFormat::Png | ImageFormat::Jpeg | ImageFormat::Gif | ImageFormat::WebP)) => {
+            let image = image::load_from_memory_with_format(bytes, format).ok()?.to_rgba8();
+            Some(ImagePreview {
+                format: format_name(format),
+                width: image.width(),
+                height: image.height(),
+                byte_size: bytes.len(),
+                image,
+            })
+        }
+        _ if mime_type.to_lowercase().contains("svg") => decode_svg(bytes),
+        _ => None,
+    }
+}
+
+fn format_name(format: ImageFormat) -> &'static str {
+    match format {
+        ImageFormat::Png => "PNG",
+        ImageFormat::Jpeg => "JPEG",
+        ImageFormat::Gif => "GIF",
+        ImageFormat::WebP => "WebP",
+        _ => "image",
+    }
+}
+
+/// Rasterize an SVG at its intrinsic size (capped so huge viewBoxes stay cheap).
+fn decode_svg(bytes: &[u8]) -> Option<ImagePreview> {
+    const MAX_SIDE: f32 = 2048.0;
+    let tree = resvg::usvg::Tree::from_data(bytes, &resvg::usvg::Options::default()).ok()?;
+    let size = tree.size();
+    let scale = (MAX_SIDE / size.width().max(size.height())).min(1.0);
+    let width = ((size.width() * scale).ceil() as u32).max(1);
+    let height = ((size.height() * scale).ceil() as u32).max(1);
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(width, height)?;
+    resvg::render(
+        &tree,
+        resvg::tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+    let image = RgbaImage::from_raw(width, height, pixmap.take_demultiplied())?;
+    Some(ImagePreview {
+        format: "SVG",
+        width: size.width().round() as u32,
+        height: size.height().round() as u32,
+        byte_size: bytes.len(),
+        image,
+    })
+}
+
+impl ImagePreview {
+    /// Largest box of cells, within `max_cols` x `max_rows`, that shows the image
+    /// at its aspect ratio without upscaling it.
+    pub fn fit_cells(&self, max_cols: u16, max_rows: u16) -> (u16, u16) {
+        let natural_cols = self.image.width().div_ceil(CELL_WIDTH_PX).max(1) as f64;
+        let natural_rows = self.image.height().div_ceil(CELL_HEIGHT_PX).max(1) as f64;
+        let scale = (max_cols as f64 / natural_cols)
+            .min(max_rows as f64 / natural_rows)
+            .min(1.0);
+        let cols = (natural_cols * scale).round().clamp(1.0, max_cols.max(1) as f64);
+        let rows = (natural_rows * scale).round().clamp(1.0, max_rows.max(1) as f64);
+        (cols as u16, rows as u16)
+    }
+
+    /// Draw the image with `▀` cells: the foreground paints the upper pixel and
+    /// the background the lower one.
+    pub fn render_halfblocks(&self, area: Rect, buf: &mut Buffer) {
+        let (cols, rows) = self.fit_cells(area.width, area.height);
+        let scaled = image::imageops::resize(
+            &self.image,
+            cols as u32,
+            rows as u32 * 2,
+            FilterType::Triangle,
+        );
+        for y in 0..rows {
+            for x in 0..cols {
+                let top = blend_on_black(scaled.get_pixel(x as u32, y as u32 * 2).0);
+                let bottom = blend_on_black(scaled.get_pixel(x as u32, y as u32 * 2 + 1).0);
+                buf.get_mut(area.x + x, area.y + y)
+                    .set_symbol("▀")
+                    .set_fg(top)
+                    .set_bg(bottom);
+            }
+        }
+    }
+
+    /// Escape sequence that draws the image with the cursor at the top-left
+    /// cell of a `cols` x `rows` box.
+    pub fn encode(&self, protocol: ImageProtocol, cols: u16, rows: u16) -> Option<String> {
+        match protocol {
+            ImageProtocol::Kitty => Some(kitty_sequence(&self.png()?, cols, rows)),
+            ImageProtocol::Iterm2 => Some(iterm2_sequence(&self.png()?, cols, rows)),
+            ImageProtocol::Sixel => {
+                let scaled = image::imageops::resize(
+                    &self.image,
+                    cols as u32 * CELL_WIDTH_PX,
+                    rows as u32 * CELL_HEIGHT_PX,
+                    FilterType::Triangle,
+                );
+                Some(sixel_sequence(&scaled))
+            }
+            ImageProtocol::Auto | ImageProtocol::Halfblocks => None,
+        }
+    }
+
+    fn png(&self) -> Option<Vec<u8>> {
+        let mut out = Cursor::new(Vec::new());
+        self.image.write_to(&mut out, ImageFormat::Png).ok()?;
+        Some(out.into_inner())
+    }
+}
+
+fn blend_on_black([r, g, b, a]: [u8; 4]) -> Color {
+    let mix = |c: u8| (c as u16 * a as u16 / 255) as u8;
+    Color::Rgb(mix(r), mix(g), mix(b))
+}
+
+/// Kitty graphics protocol: transmit-and-display a PNG scaled to the cell box.
+fn kitty_sequence(png: &[u8], cols: u16, rows: u16) -> String {
+    let payload = BASE64_STANDARD.encode(png);
+    let chunks: Vec<&str> = payload
+        .as_bytes()
+        .chunks(KITTY_CHUNK)
+        .map(|c| std::str::from_utf8(c).unwrap_or_default())
+        .collect();
+    let mut out = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        if i == 0 {
+            out.push_str(&format!(
+                "\x1b_Ga=T,f=100,q=2,C=1,c={},r={},m={};{}\x1b\\",
+                cols, rows, more, chunk
+            ));
+        } else {
+            out.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
+        }
+    }
+    out
+}
+
+/// Remove every image placed with the kitty protocol.
+pub fn kitty_clear_sequence() -> &'static str {
+    "\x1b_Ga=d,d=A,q=2\x1b\\"
+}
+
+/// iTerm2 inline image protocol (also understood by WezTerm).
+fn iterm2_sequence(png: &[u8], cols: u16, rows: u16) -> String {
+    format!(
+        "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=1:{}\x07",
+        png.len(),
+        cols,
+        rows,
+        BASE64_STANDARD.encode(png)
+    )
+}
+
+/// Encode an image as DEC sixel using a fixed 6x6x6 color cube.
+fn sixel_sequence(image: &RgbaImage) -> String {
+    let (width, height) = image.dimensions();
+    let level = |c: u8| (c as u16 * 5 / 255) as usize;
+    let index_of = |x: u32, y: u32| -> Option<usize> {
+        let [r, g, b, a] = image.get_pixel(x, y).0;
+        (a >= 128).then(|| level(r) * 36 + level(g) * 6 + level(b))
+    };
+
+    let mut out = format!("\x1bPq\"1;1;{};{}", width, height);
+    for i in 0..216 {
+        let pct = |v: usize| v * 100 / 5;
+        out.push_str(&format!("#{};2;{};{};{}", i, pct(i / 36), pct(i / 6 % 6), pct(i % 6)));
+    }
+
+    for band in (0..height).step_by(6) {
+        let mut used = [false; 216];
+        for y in band..(band + 6).min(height) {
+            for x in 0..width {
+                if let Some(i) = index_of(x, y) {
+                    used[i] = true;
+                }
+            }
+        }
+        for (color, _) in used.iter().enumerate().filter(|(_, &u)| u) {
+            out.push_str(&format!("#{}", color));
+            let mut run: Option<(u8, usize)> = None;
+            for x in 0..width {
+                let mut bits = 0u8;
+                for dy in 0..6 {
+                    if band + dy < height && index_of(x, band + dy) == Some(color) {
+                        bits |= 1 << dy;
+                    }
+                }
+                run = match run {
+                    Some((b, n)) if b == bits => Some((b, n + 1)),
+                    Some((b, n)) => {
+                        push_sixel_run(&mut out, b, n);
+                        Some((bits, 1))
+                    }
+                    None => Some((bits, 1)),
+                };
+            }
+            if let Some((b, n)) = run {
+                push_sixel_run(&mut out, b, n);
+            }
+            out.push('$');
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+fn push_sixel_run(out: &mut String, bits: u8, count: usize) {
+    let c = (0x3f + bits) as char;
+    if count > 3 {
+        out.push_str(&format!("!{}{}", count, c));
+    } else {
+        out.extend(std::iter::repeat_n(c, count));
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png_bytes(width: u32, height: u32) -> Vec<u8> {
+        let image = RgbaImage::from_pixel(width, height, image::Rgba([255, 0, 0, 255]));
+        let mut out = Cursor::new(Vec::new());
+        image.write_to(&mut out, ImageFormat::Png).unwrap();
+        out.into_inner()
+    }
+
+    #[test]
+    fn decode_png_metadata() {
+        let bytes = png_bytes(40, 20);
+        let preview = decode("image/png", &bytes).expect("png should decode");
+        assert_eq!(preview.format, "PNG");
+        assert_eq!((preview.width, preview.height), (40, 20));
+        assert_eq!(preview.byte_size, bytes.len());
+    }
+
+    #[test]
+    fn decode_svg_by_mime_type() {
+        let svg = br#"<svg xmlns="http://www.w3.org/2000/svg" width="24" height="12"><rect width="24" height="12" fill="blue"/></svg>"#;
+        let preview = decode("image/svg+xml", svg).expect("svg should decode");
+        assert_eq!(preview.format, "SVG");
+        assert_eq!((preview.width, preview.height), (24, 12));
+    }
+
+    #[test]
+    fn decode_rejects_garbage() {
+        assert!(decode("image/png", b"definitely not an image").is_none());
+    }
+
+    #[test]
+    fn fit_cells_keeps_aspect_and_never_upscales() {
+        let preview = decode("image/png", &png_bytes(16, 16)).unwrap();
+        assert_eq!(preview.fit_cells(80, 40), (2, 1));
+
+        let preview = decode("image/png", &png_bytes(800, 160)).unwrap();
+        assert_eq!(preview.fit_cells(50, 40), (50, 5));
+    }
+
+    #[test]
+    fn halfblocks_fill_fitted_area() {
+        let preview = decode("image/png", &png_bytes(16, 32)).unwrap();
+        let area = Rect::new(0, 0, 10, 10);
+        let mut buf = Buffer::empty(area);
+        preview.render_halfblocks(area, &mut buf);
+        assert_eq!(buf.get(0, 0).symbol(), "▀");
+        assert_eq!(buf.get(0, 0).fg, Color::Rgb(255, 0, 0));
+        assert_eq!(buf.get(2, 0).symbol(), " ");
+    }
+
+    #[test]
+    fn graphics_sequences_are_framed() {
+        let preview = decode("image/png", &png_bytes(8, 8)).unwrap();
+        let kitty = preview.encode(ImageProtocol::Kitty, 1, 1).unwrap();
+        assert!(kitty.starts_with("\x1b_Ga=T,f=100"));
+        assert!(kitty.ends_with("\x1b\\"));
+        let iterm = preview.encode(ImageProtocol::Iterm2, 1, 1).unwrap();
+        assert!(iterm.starts_with("\x1b]1337;File=inline=1"));
+        let sixel = preview.encode(ImageProtocol::Sixel, 1, 1).unwrap();
+        assert!(sixel.starts_with("\x1bPq\"1;1;8;16"));
+        assert!(sixel.ends_with("-\x1b\\"));
+        assert!(preview.encode(ImageProtocol::Halfblocks, 1, 1).is_none());
+    }
+
+    #[test]
+    fn explicit_protocol_is_not_redetected() {
+        assert_eq!(ImageProtocol::Sixel.detect(), ImageProtocol::Sixel);
+        assert!(!ImageProtocol::Halfblocks.is_graphics());
+    }
+}
@@ -0,0 +1,302 @@
+use std::cell::Cell;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use jaq_core::box_iter::box_once;
+use jaq_core::load::lex::{StrPart, Tok, Token};
+use jaq_core::{load, Ctx, Error, Exn, Filter, FilterT, Native, RcIter};
+use jaq_json::Val;
+use jsonpath_rust::JsonPath;
+use serde_json::Value;
+
+/// Most values a query may produce; `repeat(1)` would otherwise never end.
+pub const MAX_OUTPUTS: usize = 1000;
+
+/// Time a query may spend producing values; it runs on every keystroke.
+const TIME_BUDGET: Duration = Duration::from_millis(250);
+
+/// Values produced by a query, and whether it was stopped before finishing.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Outputs {
+    pub values: Vec<Value>,
+    /// Set when `MAX_OUTPUTS` or the time budget was reached.
+    pub truncated: bool,
+}
+
+// ── Step budget ───────────────────────────────────────────────────────────────
+
+// jq evaluation cannot be interrupted from outside, and a filter such as
+// `[repeat(1)]` never yields, so checking the clock between outputs is not
+// enough. Every way a query can loop without end goes through a recursive
+// definition or `range`: user definitions are rejected, and the standard
+// generators below call `_tick`, which fails once the deadline has passed.
+
+thread_local! {
+    /// When the query running on this thread has to stop.
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+    /// Whether `_tick` stopped the query running on this thread.
+    static EXPIRED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Standard generators that may never end, with a `_tick` in each step.
+const TICKED_DEFS: &str = r#"
+def repeat(f): def rec: f, (_tick | rec); rec;
+def recurse(f): def rec: ., (f | _tick | rec); rec;
+def while(cond; update): def rec: if cond then ., (update | _tick | rec) else empty end; rec;
+def until(cond; update): def rec: if cond then . else update | _tick | rec end; rec;
+"#;
+
+/// The standard `range/3`, which `ticking_range` wraps.
+static RANGE: OnceLock<Native<Val>> = OnceLock::new();
+
+fn tick<'a>() -> Result<(), Exn<'a, Val>> {
+    let expired = DEADLINE.get().is_some_and(|deadline| Instant::now() > deadline);
+    if expired {
+        EXPIRED.set(true);
+        return Err(Exn::from(Error::str("query ran out of time")));
+    }
+    Ok(())
+}
+
+fn ticking_range() -> Native<Val> {
+    Native::new(|lut, cv| {
+        let range = RANGE.get().expect("range is registered before compiling");
+        Box::new(range.run(lut, cv).map(|value| tick().and(value)))
+    })
+}
+
+/// Whether the jq program defines functions of its own.
+fn defines_functions(tokens: &[Token<&str>]) -> bool {
+    tokens.iter().any(|Token(text, tok)| match tok {
+        Tok::Word => *text == "def",
+        Tok::Block(inner) => defines_functions(inner),
+        Tok::Str(parts) => parts
+            .iter()
+            .any(|part| matches!(part, StrPart::Term(term) if defines_functions(std::slice::from_ref(term)))),
+        _ => false,
+    })
+}
+
+// ── Queries ───────────────────────────────────────────────────────────────────
+
+/// A compiled body query: jq syntax (`.items[] | .id`), or JSONPath when the
+/// expression starts with `$` (`$.items[*].id`).
+pub enum JsonQuery {
+    Jq {
+        filter: Filter<Native<Val>>,
+        /// Set once a run used up the time budget; later runs stop at once,
+        /// so a search over many entries spends the budget only once.
+        exhausted: Cell<bool>,
+    },
+    JsonPath(String),
+}
+
+impl std::fmt::Debug for JsonQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Jq { .. } => f.write_str("JsonQuery::Jq(..)"),
+            Self::JsonPath(path) => write!(f, "JsonQuery::JsonPath({:?})", path),
+        }
+    }
+}
+
+impl JsonQuery {
+    pub fn parse(expr: &str) -> Result<Self> {
+        let expr = expr.trim();
+        if expr.starts_with('$') {
+            // Validate up front so typing errors show while editing.
+            Value::Null
+                .query(expr)
+                .map_err(|e| anyhow!("invalid JSONPath: {}", e))?;
+            return Ok(Self::JsonPath(expr.to_string()));
+        }
+
+        if load::Lexer::new(expr).lex().is_ok_and(|tokens| defines_functions(&tokens)) {
+            return Err(anyhow!("`def` is not supported in body queries"));
+        }
+        let program = load::File { code: expr, path: () };
+        // Replaced in place, as later definitions such as `recurse/0` use them.
+        let mut ticked = load::parse(TICKED_DEFS, |p| p.defs()).unwrap_or_default();
+        let defs = jaq_std::defs()
+            .map(|def| {
+                match ticked.iter().position(|t| t.name == def.name && t.args.len() == def.args.len()) {
+                    Some(i) => ticked.remove(i),
+                    None => def,
+                }
+            })
+            .chain(jaq_json::defs());
+        let loader = load::Loader::new(defs);
+        let arena = load::Arena::default();
+        let modules = loader
+            .load(&arena, program)
+            .map_err(|_| anyhow!("invalid jq expression"))?;
+        let funs = jaq_std::funs().chain(jaq_json::funs()).map(|(name, args, native)| {
+            if name == "range" && args.len() == 3 {
+                RANGE.get_or_init(|| native);
+                (name, args, ticking_range())
+            } else {
+                (name, args, native)
+            }
+        });
+        let tick_fun = ("_tick", jaq_std::v(0), Native::new(|_, cv| box_once(tick().map(|()| cv.1))));
+        let filter = jaq_core::Compiler::default()
+            .with_funs(funs.chain([tick_fun]))
+            .compile(modules)
+            .map_err(|errs| {
+                let names: Vec<&str> = errs
+                    .iter()
+                    .flat_map(|(_, undefined)| undefined.iter().map(|(name, _)| *name))
+                    .collect();
+                anyhow!("undefined in jq expression: {}", names.join(", "))
+            })?;
+        Ok(Self::Jq {
+            filter,
+            exhausted: Cell::new(false),
+        })
+    }
+
+    /// The values the query produces for `input`, stopping after
+    /// `MAX_OUTPUTS` of them or once the time budget is spent, also in the
+    /// middle of computing a value.
+    pub fn run(&self, input: &Value) -> Result<Outputs> {
+        let mut outputs = Outputs::default();
+        match self {
+            Self::Jq { exhausted, .. } if exhausted.get() => outputs.truncated = true,
+            Self::Jq { filter, exhausted } => {
+                let inputs = RcIter::new(core::iter::empty());
+                let deadline = Instant::now() + TIME_BUDGET;
+                DEADLINE.set(Some(deadline));
+                EXPIRED.set(false);
+                let result = (|| {
+                    for out in filter.run((Ctx::new([], &inputs), Val::from(input.clone()))) {
+                        if outputs.values.len() == MAX_OUTPUTS || Instant::now() > deadline {
+                            outputs.truncated = true;
+                            break;
+                        }
+                        match out {
+                            Ok(value) => outputs.values.push(value.into()),
+                            Err(_) if EXPIRED.get() => break,
+                            Err(e) => return Err(anyhow!("{}", e)),
+                        }
+                    }
+                    Ok(())
+                })();
+                DEADLINE.set(None);
+                if EXPIRED.get() || Instant::now() > deadline {
+                    outputs.truncated = true;
+                    exhausted.set(true);
+                }
+                result?;
+            }
+            Self::JsonPath(path) => {
+                let found = input.query(path).map_err(|e| anyhow!("{}", e))?;
+                outputs.truncated = found.len() > MAX_OUTPUTS;
+                outputs.values = found.into_iter().take(MAX_OUTPUTS).cloned().collect();
+            }
+        }
+        Ok(outputs)
+    }
+
+    /// Predicate form used by search: true if any output is truthy (neither
+    /// `null` nor `false`). Errors count as no match.
+    pub fn matches(&self, input: &Value) -> bool {
+        self.run(input).is_ok_and(|out| {
+            out.values
+                .iter()
+                .any(|v| !matches!(v, Value::Null | Value::Bool(false)))
+        })
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn body() -> Value {
+        json!({"items": [{"id": 1, "ok": true}, {"id": 2, "ok": false}], "error": null})
+    }
+
+    #[test]
+    fn jq_projection() {
+        let q = JsonQuery::parse(".items[] | .id").unwrap();
+        assert_eq!(q.run(&body()).unwrap().values, vec![json!(1), json!(2)]);
+
+        let q = JsonQuery::parse("[.items[] | select(.ok)] | length").unwrap();
+        assert_eq!(q.run(&body()).unwrap().values, vec![json!(1)]);
+    }
+
+    #[test]
+    fn jsonpath_projection() {
+        let q = JsonQuery::parse("$.items[*].id").unwrap();
+        assert_eq!(q.run(&body()).unwrap().values, vec![json!(1), json!(2)]);
+
+        let q = JsonQuery::parse("$..[?(@.ok == true)].id").unwrap();
+        assert_eq!(q.run(&body()).unwrap().values, vec![json!(1)]);
+    }
+
+    #[test]
+    fn endless_generators_are_cut_off() {
+        let out = JsonQuery::parse("repeat(1)").unwrap().run(&body()).unwrap();
+        assert!(out.truncated);
+        assert!(!out.values.is_empty() && out.values.len() <= MAX_OUTPUTS);
+
+        let out = JsonQuery::parse("range(1e9)").unwrap().run(&body()).unwrap();
+        assert!(out.truncated);
+        assert!(!JsonQuery::parse(".items[]").unwrap().run(&body()).unwrap().truncated);
+    }
+
+    #[test]
+    fn filters_that_never_yield_are_cut_off() {
+        for expr in [
+            "[repeat(1)]",
+            "last(range(1e12))",
+            "[limit(1000000000000; repeat(1))] | length",
+            "[recurse(. + 1)] | length",
+            "until(false; .)",
+            "reduce range(1e12) as $x (0; . + $x)",
+            "try [repeat(1)] catch 0",
+        ] {
+            let started = Instant::now();
+            let query = JsonQuery::parse(expr).unwrap();
+            let out = query.run(&json!(0)).unwrap();
+            assert!(out.truncated, "{}", expr);
+            assert!(started.elapsed() < TIME_BUDGET * 4, "{} took {:?}", expr, started.elapsed());
+            // A search running the same query over more entries stops at once.
+            let started = Instant::now();
+            assert!(!query.matches(&json!(0)));
+            assert!(started.elapsed() < TIME_BUDGET / 10);
+        }
+        assert!(JsonQuery::parse("def f: f; f").is_err());
+        assert!(JsonQuery::parse(r#""\(def f: 1; f)""#).is_err());
+        assert_eq!(JsonQuery::parse(r#"[.def, "def"]"#).unwrap().run(&json!({"def": 1})).unwrap().values, vec![json!([1, "def"])]);
+        assert_eq!(JsonQuery::parse("[range(3)], [limit(2; repeat(1))]").unwrap().run(&json!(null)).unwrap().values, vec![json!([0, 1, 2]), json!([1, 1])]);
+    }
+
+    #[test]
+    fn invalid_expressions_are_errors() {
+        assert!(JsonQuery::parse(".items[").is_err());
+        assert!(JsonQuery::parse("nosuchfn(1)").is_err());
+        assert!(JsonQuery::parse("$.items[").is_err());
+    }
+
+    #[test]
+    fn runtime_error_is_reported() {
+        let q = JsonQuery::parse(".items + 1").unwrap();
+        assert!(q.run(&body()).is_err());
+        assert!(!q.matches(&body()));
+    }
+
+    #[test]
+    fn predicate_truthiness() {
+        assert!(!JsonQuery::parse(".error != null").unwrap().matches(&body()));
+        assert!(JsonQuery::parse(".error == null").unwrap().matches(&body()));
+        assert!(JsonQuery::parse(".items[].ok").unwrap().matches(&body()));
+        assert!(!JsonQuery::parse(".missing").unwrap().matches(&body()));
+        assert!(JsonQuery::parse("$.items[?(@.id == 2)]").unwrap().matches(&body()));
+        assert!(!JsonQuery::parse("$.items[?(@.id == 9)]").unwrap().matches(&body()));
+    }
+}
@@ -0,0 +1,480 @@
+use ratatui::prelude::*;
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// One step from a JSON value to a child.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathSeg {
+    Key(String),
+    Index(usize),
+}
+
+/// Render a path in jq style, e.g. `.data.items[3].id`.
+pub fn format_path(path: &[PathSeg]) -> String {
+    if path.is_empty() {
+        return ".".to_string();
+    }
+    let mut out = String::new();
+    for seg in path {
+        match seg {
+            PathSeg::Key(k) if is_identifier(k) => {
+                out.push('.');
+                out.push_str(k);
+            }
+            PathSeg::Key(k) => out.push_str(&format!(".[{}]", Value::String(k.clone()))),
+            PathSeg::Index(i) => out.push_str(&format!("[{}]", i)),
+        }
+    }
+    out
+}
+
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Follow `path` from `root`.
+pub fn value_at<'a>(root: &'a Value, path: &[PathSeg]) -> Option<&'a Value> {
+    path.iter().try_fold(root, |v, seg| match seg {
+        PathSeg::Key(k) => v.get(k),
+        PathSeg::Index(i) => v.get(i),
+    })
+}
+
+/// A visible line of the tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    pub path: Vec<PathSeg>,
+    pub depth: usize,
+    pub expandable: bool,
+    pub expanded: bool,
+}
+
+/// Collapsible view over a parsed JSON body.
+#[derive(Debug, Clone)]
+pub struct JsonTree {
+    root: Value,
+    expanded: HashSet<Vec<PathSeg>>,
+    rows: Vec<Row>,
+    pub cursor: usize,
+    /// Key-search prompt buffer; `Some` while the prompt is open.
+    pub find_input: Option<String>,
+    last_find: String,
+    /// One-line feedback shown in the pane title (copy result, no match, …).
+    pub message: Option<String>,
+}
+
+impl JsonTree {
+    /// Open a tree with the root and its direct children visible.
+    pub fn new(root: Value) -> Self {
+        let mut tree = Self {
+            root,
+            expanded: HashSet::new(),
+            rows: Vec::new(),
+            cursor: 0,
+            find_input: None,
+            last_find: String::new(),
+            message: None,
+        };
+        tree.expanded.insert(Vec::new());
+        tree.rebuild();
+        tree
+    }
+
+    pub fn rows(&self) -> &[Row] {
+        &self.rows
+    }
+
+    pub fn root(&self) -> &Value {
+        &self.root
+    }
+
+    pub fn cursor_path(&self) -> &[PathSeg] {
+        self.rows.get(self.cursor).map_or(&[], |r| r.path.as_slice())
+    }
+
+    pub fn cursor_value(&self) -> Option<&Value> {
+        value_at(&self.root, self.cursor_path())
+    }
+
+    fn rebuild(&mut self) {
+        let mut rows = Vec::new();
+        let mut path = Vec::new();
+        collect_rows(&self.root, &mut path, &self.expanded, &mut rows);
+        self.rows = rows;
+        self.cursor = self.cursor.min(self.rows.len().saturating_sub(1));
+    }
+
+    /// Move the cursor to `path`, if that row is visible.
+    fn select_path(&mut self, path: &[PathSeg]) {
+        if let Some(i) = self.rows.iter().position(|r| r.path == path) {
+            self.cursor = i;
+        }
+    }
+
+    pub fn move_cursor(&mut self, delta: i32) {
+        let max = self.rows.len().saturating_sub(1) as i64;
+        self.cursor = (self.cursor as i64 + delta as i64).clamp(0, max) as usize;
+    }
+
+    pub fn cursor_first(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn cursor_last(&mut self) {
+        self.cursor = self.rows.len().saturating_sub(1);
+    }
+
+    pub fn toggle(&mut self) {
+        let Some(row) = self.rows.get(self.cursor).cloned() else {
+            return;
+        };
+        if !row.expandable {
+            return;
+        }
+        if !self.expanded.remove(&row.path) {
+            self.expanded.insert(row.path.clone());
+        }
+        self.rebuild();
+        self.select_path(&row.path);
+    }
+
+    pub fn expand(&mut self) {
+        match self.rows.get(self.cursor) {
+            Some(row) if row.expandable && !row.expanded => self.toggle(),
+            // Already open: step into the first child.
+            Some(row) if row.expanded => self.move_cursor(1),
+            _ => {}
+        }
+    }
+
+    /// Collapse the node under the cursor, or jump to its parent if it is a leaf
+    /// or already collapsed.
+    pub fn collapse(&mut self) {
+        let Some(row) = self.rows.get(self.cursor).cloned() else {
+            return;
+        };
+        if row.expanded {
+            self.toggle();
+        } else if let Some((_, parent)) = row.path.split_last() {
+            let parent = parent.to_vec();
+            self.select_path(&parent);
+        }
+    }
+
+    /// Expand every container shallower than `depth` and collapse the rest.
+    /// `depth == 0` collapses everything, root included.
+    pub fn expand_to_depth(&mut self, depth: usize) {
+        let keep = self.cursor_path().to_vec();
+        self.expanded.clear();
+        let mut path = Vec::new();
+        collect_containers(&self.root, &mut path, &mut |p: &[PathSeg]| {
+            if p.len() < depth {
+                self.expanded.insert(p.to_vec());
+            }
+        });
+        self.rebuild();
+        // Keep the cursor on the same node, or its nearest visible ancestor.
+        for len in (0..=keep.len()).rev() {
+            if self.rows.iter().any(|r| r.path == keep[..len]) {
+                self.select_path(&keep[..len]);
+                break;
+            }
+        }
+    }
+
+    pub fn expand_all(&mut self) {
+        self.expand_to_depth(usize::MAX);
+    }
+
+    // ── Jump to key ─────────────────────────────────────────────────────────
+
+    pub fn enter_find(&mut self) {
+        self.find_input = Some(String::new());
+    }
+
+    pub fn push_find_char(&mut self, c: char) {
+        if let Some(input) = self.find_input.as_mut() {
+            input.push(c);
+        }
+    }
+
+    pub fn pop_find_char(&mut self) {
+        if let Some(input) = self.find_input.as_mut() {
+            input.pop();
+        }
+    }
+
+    pub fn cancel_find(&mut self) {
+        self.find_input = None;
+    }
+
+    pub fn confirm_find(&mut self) {
+        if let Some(query) = self.find_input.take() {
+            self.last_find = query;
+            self.find_next();
+        }
+    }
+
+    /// Jump to the next key (in document order, wrapping) containing the last
+    /// query, case-insensitively, expanding its ancestors.
+    pub fn find_next(&mut self) {
+        if self.last_find.is_empty() {
+            return;
+        }
+        let needle = self.last_find.to_lowercase();
+        let mut all = Vec::new();
+        let mut path = Vec::new();
+        collect_paths(&self.root, &mut path, &mut all);
+
+        let current = self.cursor_path().to_vec();
+        let start = all.iter().position(|p| *p == current).map_or(0, |i| i + 1);
+        let hit = all
+            .iter()
+            .cycle()
+            .skip(start)
+            .take(all.len())
+            .find(|p| matches!(p.last(), Some(PathSeg::Key(k)) if k.to_lowercase().contains(&needle)))
+            .cloned();
+
+        match hit {
+            Some(target) => {
+                for len in 0..target.len() {
+                    self.expanded.insert(target[..len].to_vec());
+                }
+                self.rebuild();
+                self.select_path(&target);
+                self.message = None;
+            }
+            None => self.message = Some(format!("no key matching \"{}\"", self.last_find)),
+        }
+    }
+
+    /// Pretty-printed JSON of the node under the cursor.
+    pub fn cursor_subtree_json(&self) -> Option<String> {
+        self.cursor_value()
+            .and_then(|v| serde_json::to_string_pretty(v).ok())
+    }
+
+    /// Render `height` rows starting at `offset`, highlighting the cursor row.
+    pub fn render_lines(&self, offset: usize, height: usize) -> Vec<Line<'static>> {
+        self.rows
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(height)
+            .map(|(i, row)| {
+                let line = self.render_row(row);
+                if i == self.cursor {
+                    line.patch_style(Style::default().reversed())
+                } else {
+                    line
+                }
+            })
+            .collect()
+    }
+
+    fn render_row(&self, row: &Row) -> Line<'static> {
+        let value = value_at(&self.root, &row.path).unwrap_or(&Value::Null);
+        let mut spans = vec![Span::raw("  ".repeat(row.depth))];
+        spans.push(Span::styled(
+            match (row.expandable, row.expanded) {
+                (true, true) => "▾ ",
+                (true, false) => "▸ ",
+                _ => "  ",
+            },
+            Style::default().fg(Color::DarkGray),
+        ));
+        match row.path.last() {
+            Some(PathSeg::Key(k)) => {
+                spans.push(Span::styled(k.clone(), Style::default().fg(Color::LightBlue)));
+                spans.push(Span::raw(": "));
+            }
+            Some(PathSeg::Index(i)) => {
+                spans.push(Span::styled(format!("[{}]", i), Style::default().fg(Color::DarkGray)));
+                spans.push(Span::raw(": "));
+            }
+            None => {}
+        }
+        spans.push(value_summary(value, row.expanded));
+        Line::from(spans)
+    }
+}
+
+fn value_summary(value: &Value, expanded: bool) -> Span<'static> {
+    let dim = Style::default().fg(Color::DarkGray);
+    match value {
+        Value::Object(map) if expanded => Span::styled(format!("{{{}}}", map.len()), dim),
+        Value::Object(map) => Span::styled(format!("{{…}} {} keys", map.len()), dim),
+        Value::Array(items) if expanded => Span::styled(format!("[{}]", items.len()), dim),
+        Value::Array(items) => Span::styled(format!("[…] {} items", items.len()), dim),
+        Value::String(s) => Span::styled(Value::String(s.clone()).to_string(), Style::default().fg(Color::Green)),
+        Value::Number(n) => Span::styled(n.to_string(), Style::default().fg(Color::LightCyan)),
+        Value::Bool(b) => Span::styled(b.to_string(), Style::default().fg(Color::Yellow)),
+        Value::Null => Span::styled("null", Style::default().fg(Color::Magenta)),
+    }
+}
+
+fn children(value: &Value) -> Vec<(PathSeg, &Value)> {
+    match value {
+        Value::Object(map) => map.iter().map(|(k, v)| (PathSeg::Key(k.clone()), v)).collect(),
+        Value::Array(items) => items.iter().enumerate().map(|(i, v)| (PathSeg::Index(i), v)).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn is_container(value: &Value) -> bool {
+    matches!(value, Value::Object(m) if !m.is_empty()) || matches!(value, Value::Array(a) if !a.is_empty())
+}
+
+fn collect_rows(
+    value: &Value,
+    path: &mut Vec<PathSeg>,
+    expanded: &HashSet<Vec<PathSeg>>,
+    rows: &mut Vec<Row>,
+) {
+    let expandable = is_container(value);
+    let is_open = expandable && expanded.contains(path.as_slice());
+    rows.push(Row {
+        path: path.clone(),
+        depth: path.len(),
+        expandable,
+        expanded: is_open,
+    });
+    if is_open {
+        for (seg, child) in children(value) {
+            path.push(seg);
+            collect_rows(child, path, expanded, rows);
+            path.pop();
+        }
+    }
+}
+
+fn collect_containers(value: &Value, path: &mut Vec<PathSeg>, f: &mut impl FnMut(&[PathSeg])) {
+    if is_container(value) {
+        f(path);
+        for (seg, child) in children(value) {
+            path.push(seg);
+            collect_containers(child, path, f);
+            path.pop();
+        }
+    }
+}
+
+fn collect_paths(value: &Value, path: &mut Vec<PathSeg>, out: &mut Vec<Vec<PathSeg>>) {
+    out.push(path.clone());
+    for (seg, child) in children(value) {
+        path.push(seg);
+        collect_paths(child, path, out);
+        path.pop();
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample() -> JsonTree {
+        JsonTree::new(json!({
+            "data": {
+                "items": [{"id": 1}, {"id": 2}, {"id": 3}, {"id": 4, "tags": ["a"]}],
+                "total": 4
+            },
+            "error": null,
+            "weird key": true
+        }))
+    }
+
+    #[test]
+    fn path_formatting() {
+        let path = vec![
+            PathSeg::Key("data".into()),
+            PathSeg::Key("items".into()),
+            PathSeg::Index(3),
+            PathSeg::Key("id".into()),
+        ];
+        assert_eq!(format_path(&path), ".data.items[3].id");
+        assert_eq!(format_path(&[PathSeg::Key("weird key".into())]), ".[\"weird key\"]");
+        assert_eq!(format_path(&[]), ".");
+    }
+
+    #[test]
+    fn new_tree_shows_root_children() {
+        let tree = sample();
+        let labels: Vec<String> = tree.rows().iter().map(|r| format_path(&r.path)).collect();
+        assert_eq!(labels, vec![".", ".data", ".error", ".[\"weird key\"]"]);
+        assert!(tree.rows()[0].expanded);
+        assert!(!tree.rows()[1].expanded);
+    }
+
+    #[test]
+    fn toggle_expand_and_collapse_to_parent() {
+        let mut tree = sample();
+        tree.move_cursor(1);
+        tree.toggle();
+        assert_eq!(tree.rows().len(), 6);
+        tree.expand(); // already open: step into first child
+        assert_eq!(format_path(tree.cursor_path()), ".data.items");
+        tree.collapse(); // collapsed leaf-ish node: go to parent
+        assert_eq!(format_path(tree.cursor_path()), ".data");
+        tree.collapse();
+        assert_eq!(tree.rows().len(), 4);
+    }
+
+    #[test]
+    fn expand_to_depth_and_all() {
+        let mut tree = sample();
+        tree.expand_to_depth(2);
+        assert!(tree.rows().iter().any(|r| format_path(&r.path) == ".data.items"));
+        assert!(!tree.rows().iter().any(|r| format_path(&r.path) == ".data.items[0]"));
+
+        tree.expand_all();
+        assert!(tree.rows().iter().any(|r| format_path(&r.path) == ".data.items[3].tags[0]"));
+
+        tree.expand_to_depth(0);
+        assert_eq!(tree.rows().len(), 1);
+    }
+
+    #[test]
+    fn find_expands_ancestors_and_wraps() {
+        let mut tree = sample();
+        tree.enter_find();
+        for c in "TAGS".chars() {
+            tree.push_find_char(c);
+        }
+        tree.confirm_find();
+        assert_eq!(format_path(tree.cursor_path()), ".data.items[3].tags");
+
+        tree.last_find = "id".to_string();
+        tree.find_next();
+        assert_eq!(format_path(tree.cursor_path()), ".data.items[0].id");
+
+        tree.last_find = "nope".to_string();
+        tree.find_next();
+        assert!(tree.message.is_some());
+    }
+
+    #[test]
+    fn subtree_json_of_cursor() {
+        let mut tree = sample();
+        tree.move_cursor(1);
+        let json: Value = serde_json::from_str(&tree.cursor_subtree_json().unwrap()).unwrap();
+        assert_eq!(json["total"], 4);
+    }
+
+    #[test]
+    fn render_marks_cursor_row() {
+        let tree = sample();
+        let lines = tree.render_lines(0, 10);
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].style.add_modifier.contains(Modifier::REVERSED));
+        let text: String = lines[1].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(text.contains("data: {…} 2 keys"));
+    }
+}
@@ -0,0 +1,266 @@
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+use base64::prelude::*;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+
+use crate::har;
+use crate::secret_scan;
+
+/// Compact-serialized JWT: base64url header and payload (both JSON objects,
+/// so both start with `eyJ`) and a signature that is empty for `alg: none`.
+pub const PATTERN: &str = r"\beyJ[A-Za-z0-9_-]+\.eyJ[A-Za-z0-9_-]+\.[A-Za-z0-9_-]*";
+
+static TOKEN: LazyLock<Regex> = LazyLock::new(|| Regex::new(PATTERN).expect("valid JWT pattern"));
+
+/// Registered claims holding NumericDate timestamps.
+const TIME_CLAIMS: &[&str] = &["exp", "iat", "nbf", "auth_time"];
+
+/// Decoded header and payload of a JWT. The signature is not verified.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Jwt {
+    pub header: serde_json::Value,
+    pub payload: serde_json::Value,
+}
+
+/// Decode `token`, or `None` unless both parts are base64url JSON objects.
+pub fn decode(token: &str) -> Option<Jwt> {
+    let mut parts = token.trim().split('.');
+    let (header, payload, _signature) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() {
+        return None;
+    }
+    let part = |s: &str| -> Option<serde_json::Value> {
+        let bytes = BASE64_URL_SAFE_NO_PAD.decode(s.trim_end_matches('=')).ok()?;
+        serde_json::from_slice(&bytes).ok().filter(serde_json::Value::is_object)
+    };
+    Some(Jwt {
+        header: part(header)?,
+        payload: part(payload)?,
+    })
+}
+
+impl Jwt {
+    pub fn alg(&self) -> Option<&str> {
+        self.header.get("alg")?.as_str()
+    }
+
+    fn claim_time(&self, name: &str) -> Option<DateTime<Utc>> {
+        let secs = self.payload.get(name)?.as_f64()?;
+        DateTime::from_timestamp(secs as i64, 0)
+    }
+
+    /// Problems worth flagging at request time `at`: unsigned tokens and
+    /// tokens outside their validity window.
+    pub fn warnings(&self, at: DateTime<Utc>) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.alg().is_some_and(|alg| alg.eq_ignore_ascii_case("none")) {
+            warnings.push("alg: none — the token is unsigned".to_string());
+        }
+        if let Some(exp) = self.claim_time("exp").filter(|exp| *exp <= at) {
+            warnings.push(format!("expired {} before the request", human_duration(at - exp)));
+        }
+        if let Some(nbf) = self.claim_time("nbf").filter(|nbf| *nbf > at) {
+            warnings.push(format!("not valid until {} after the request", human_duration(nbf - at)));
+        }
+        warnings
+    }
+
+    /// Pretty-printed header lines.
+    pub fn header_lines(&self) -> Vec<String> {
+        pretty_lines(&self.header)
+    }
+
+    /// Pretty-printed payload lines, with a note such as
+    /// `2024-06-01 13:00:00 UTC, 1h after the request` on the lines of
+    /// top-level time claims.
+    pub fn payload_lines(&self, at: DateTime<Utc>) -> Vec<(String, Option<String>)> {
+        pretty_lines(&self.payload)
+            .into_iter()
+            .map(|line| {
+                let note = line
+                    .strip_prefix("  \"")
+                    .and_then(|rest| rest.split_once("\": "))
+                    .filter(|(name, _)| TIME_CLAIMS.contains(name))
+                    .and_then(|(name, _)| self.claim_time(name))
+                    .map(|time| format!("{}, {}", time.format("%Y-%m-%d %H:%M:%S UTC"), relative(time, at)));
+                (line, note)
+            })
+            .collect()
+    }
+}
+
+fn pretty_lines(value: &serde_json::Value) -> Vec<String> {
+    serde_json::to_string_pretty(value)
+        .unwrap_or_default()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+/// `time` relative to the request, e.g. `5m before the request`.
+fn relative(time: DateTime<Utc>, at: DateTime<Utc>) -> String {
+    match time.cmp(&at) {
+        std::cmp::Ordering::Less => format!("{} before the request", human_duration(at - time)),
+        std::cmp::Ordering::Equal => "at the request".to_string(),
+        std::cmp::Ordering::Greater => format!("{} after the request", human_duration(time - at)),
+    }
+}
+
+/// The two largest units of a duration: `2d 3h`, `1h 5m`, `42s`.
+pub fn human_duration(duration: chrono::Duration) -> String {
+    let secs = duration.num_seconds().unsigned_abs();
+    let units = [(86_400, "d"), (3_600, "h"), (60, "m"), (1, "s")];
+    let Some(first) = units.iter().position(|(size, _)| secs >= *size) else {
+        return "0s".to_string();
+    };
+    units[first..]
+        .iter()
+        .take(2)
+        .scan(secs, |rest, (size, unit)| {
+            let n = *rest / size;
+            *rest %= size;
+            Some((n, unit))
+        })
+        .enumerate()
+        .filter(|(i, (n, _))| *i == 0 || *n > 0)
+        .map(|(_, (n, unit))| format!("{}{}", n, unit))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// When the request was sent, the reference for `exp`/`iat`/`nbf`; now if the
+/// entry's timestamp does not parse.
+pub fn request_time(entry: &har::Entry) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(&entry.started_date_time)
+        .map(|t| t.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+/// A JWT and where in the entry it was found.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Found {
+    /// e.g. `request header Authorization` or `response body`.
+    pub location: String,
+    pub jwt: Jwt,
+}
+
+/// Every decodable JWT in the URL, headers, cookies and text bodies of
+/// `entry`, each reported once at its first location.
+pub fn find_in_entry(entry: &har::Entry) -> Vec<Found> {
+    let mut seen = HashSet::new();
+    let mut found = Vec::new();
+    for (location, text) in secret_scan::places(entry) {
+        for token in TOKEN.find_iter(&text) {
+            if !seen.insert(token.as_str().to_string()) {
+                continue;
+            }
+            if let Some(jwt) = decode(token.as_str()) {
+                found.push(Found {
+                    location: location.clone(),
+                    jwt,
+                });
+            }
+        }
+    }
+    found
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(header: serde_json::Value, payload: serde_json::Value) -> String {
+        format!(
+            "{}.{}.sig",
+            BASE64_URL_SAFE_NO_PAD.encode(header.to_string()),
+            BASE64_URL_SAFE_NO_PAD.encode(payload.to_string())
+        )
+    }
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    // 2024-06-01T12:00:00Z
+    const NOON: i64 = 1_717_243_200;
+
+    #[test]
+    fn decodes_header_and_payload() {
+        let raw = token(
+            serde_json::json!({"alg": "HS256", "typ": "JWT"}),
+            serde_json::json!({"sub": "42", "exp": NOON + 3600}),
+        );
+        let jwt = decode(&raw).unwrap();
+        assert_eq!(jwt.alg(), Some("HS256"));
+        assert_eq!(jwt.payload["sub"], "42");
+        assert!(decode("eyJhbGciOi.not-json.x").is_none());
+        assert!(decode("a.b").is_none());
+    }
+
+    #[test]
+    fn time_claims_are_annotated_relative_to_the_request() {
+        let jwt = decode(&token(
+            serde_json::json!({"alg": "RS256"}),
+            serde_json::json!({"iat": NOON - 300, "exp": NOON + 3600, "nested": {"exp": 1}}),
+        ))
+        .unwrap();
+        let lines = jwt.payload_lines(at("2024-06-01T12:00:00Z"));
+        let notes: Vec<_> = lines.iter().filter_map(|(line, note)| Some((line.trim(), note.as_deref()?))).collect();
+        assert_eq!(
+            notes,
+            vec![
+                ("\"iat\": 1717242900,", "2024-06-01 11:55:00 UTC, 5m before the request"),
+                ("\"exp\": 1717246800,", "2024-06-01 13:00:00 UTC, 1h after the request"),
+            ]
+        );
+        assert!(jwt.warnings(at("2024-06-01T12:00:00Z")).is_empty());
+    }
+
+    #[test]
+    fn flags_expired_unsigned_and_premature_tokens() {
+        let jwt = decode(&token(
+            serde_json::json!({"alg": "none"}),
+            serde_json::json!({"exp": NOON - 7500, "nbf": NOON + 90}),
+        ))
+        .unwrap();
+        assert_eq!(
+            jwt.warnings(at("2024-06-01T12:00:00Z")),
+            vec![
+                "alg: none — the token is unsigned",
+                "expired 2h 5m before the request",
+                "not valid until 1m 30s after the request",
+            ]
+        );
+    }
+
+    #[test]
+    fn durations_keep_two_units() {
+        let d = chrono::Duration::seconds;
+        assert_eq!(human_duration(d(0)), "0s");
+        assert_eq!(human_duration(d(42)), "42s");
+        assert_eq!(human_duration(d(3600)), "1h");
+        assert_eq!(human_duration(d(3 * 86_400 + 7200 + 59)), "3d 2h");
+        assert_eq!(human_duration(d(-65)), "1m 5s");
+    }
+
+    #[test]
+    fn finds_tokens_anywhere_in_an_entry() {
+        let bearer = token(serde_json::json!({"alg": "HS256"}), serde_json::json!({"sub": "a"}));
+        let issued = token(serde_json::json!({"alg": "HS256"}), serde_json::json!({"sub": "b"}));
+        let authorization = format!("Bearer {}", bearer);
+        let body = format!("{{\"access_token\":\"{}\"}}", issued);
+        let entry = har::Entry::test("POST", "https://x.test/token", 200)
+            .with_request_headers(&[("Authorization", authorization.as_str())])
+            .with_request_cookies(&[("session", bearer.as_str())])
+            .with_response_body("application/json", &body);
+        let found = find_in_entry(&entry);
+        let locations: Vec<_> = found.iter().map(|f| f.location.as_str()).collect();
+        assert_eq!(locations, vec!["request header Authorization", "response body"]);
+        assert_eq!(found[1].jwt.payload["sub"], "b");
+        assert_eq!(request_time(&entry), at("2024-06-01T12:00:00Z"));
+    }
+}
@@ -1,7 +1,30 @@
 mod app;
+mod caching;
+mod charset;
+mod content_encoding;
+mod cors;
+mod duplicates;
+mod endpoints;
+mod entry_diff;
 mod event;
+mod follow;
 mod handler;
 mod har;
+mod har_diff;
+mod har_merge;
+mod hexdump;
+mod image_preview;
+mod json_query;
+mod json_tree;
+mod jwt;
+mod mock_server;
+mod openapi;
+mod recorder;
+mod redact;
+mod redirects;
+mod replay;
+mod secret_scan;
+mod security_audit;
 mod tui;
 mod ui;
 use anyhow::Context;
@@ -9,6 +32,7 @@ use clap::Parser;
 use har::Har;
 use ratatui::prelude::*;
 use std::path::PathBuf;
+use std::process::ExitCode;
 
 #[derive(clap::Parser, Debug)]
 #[command(
@@ -18,23 +42,449 @@ use std::path::PathBuf;
     long_about = "`harview` is an HTTP Archive Viewer works on the terminal written in Rust.
 By using the path of the HTTP Archive file exported from the developer tools of Web browsers 
 as the first argument, 
-you can read the file and view the HTTP communication log without opening the browser. "
+you can read the file and view the HTTP communication log without opening the browser. ",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
 )]
 struct Args {
-    #[arg(help = "Path of the HTTP Archive file to be loaded")]
-    path: PathBuf,
+    #[command(subcommand)]
+    command: Option<Commands>,
+    #[arg(
+        required = true,
+        help = "HTTP Archive file(s) to be loaded; several files are merged into one timeline"
+    )]
+    path: Vec<PathBuf>,
+    #[arg(short, long, help = "Keep watching the file and append entries as they are written")]
+    follow: bool,
+    #[command(flatten)]
+    view: ViewArgs,
 }
 
+/// Options of the interactive viewer.
+#[derive(clap::Args, Debug)]
+struct ViewArgs {
+    #[arg(
+        long,
+        value_enum,
+        default_value = "auto",
+        help = "Terminal graphics protocol used for image previews"
+    )]
+    image_protocol: image_preview::ImageProtocol,
+    #[arg(
+        long,
+        value_name = "URL",
+        help = "Default base URL for replaying requests (R), e.g. http://localhost:3000"
+    )]
+    replay_to: Option<String>,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Commands {
+    /// Compare two captures: added/removed endpoints, status changes, timing
+    /// regressions and body differences
+    Diff {
+        #[arg(help = "Baseline HAR file (e.g. the previous release)")]
+        baseline: PathBuf,
+        #[arg(help = "HAR file to compare against the baseline")]
+        current: PathBuf,
+        #[arg(long, default_value_t = 50.0, help = "Minimum slowdown in percent to report a timing regression")]
+        slower_pct: f64,
+        #[arg(long, default_value_t = 100.0, help = "Minimum slowdown in milliseconds to report a timing regression")]
+        slower_ms: f64,
+        #[arg(long, help = "Exit with status 1 when differences are found")]
+        exit_code: bool,
+        #[arg(long, help = "Open the current capture in the viewer, diffing each entry against its baseline counterpart")]
+        tui: bool,
+        #[command(flatten)]
+        view: ViewArgs,
+    },
+    /// Start a local HTTP server that answers requests with the recorded responses
+    Serve {
+        #[arg(help = "HAR file whose responses are replayed")]
+        path: PathBuf,
+        #[arg(long, default_value = "127.0.0.1", help = "Address to listen on")]
+        host: String,
+        #[arg(short, long, default_value_t = 8080, help = "Port to listen on")]
+        port: u16,
+        #[arg(long, value_name = "NAME", help = "Query parameter to ignore when matching (repeatable, `*` for all)")]
+        ignore_query: Vec<String>,
+        #[arg(long, help = "Also require the recorded request headers to match")]
+        match_headers: bool,
+        #[arg(long, value_name = "NAME", help = "Header to leave out of --match-headers (repeatable)")]
+        ignore_header: Vec<String>,
+    },
+    /// Run a local HTTP forward proxy and record the traffic through it as HAR
+    Record {
+        #[arg(long, default_value = "127.0.0.1:8080", help = "Address the proxy listens on")]
+        listen: std::net::SocketAddr,
+        #[arg(short, long, help = "HAR file written when recording stops")]
+        output: PathBuf,
+        #[arg(long, help = "Show the capture live in the viewer; quitting it stops recording")]
+        tui: bool,
+        #[command(flatten)]
+        view: ViewArgs,
+    },
+    /// Combine captures into one HAR sorted by time, keeping shared pages once
+    Merge {
+        #[arg(required = true, num_args = 2.., help = "HAR files to merge")]
+        paths: Vec<PathBuf>,
+        #[arg(short, long, help = "Merged HAR file to write")]
+        output: PathBuf,
+    },
+    /// Write a copy of a capture with credentials and PII masked
+    Redact {
+        #[arg(help = "HAR file to sanitize")]
+        path: PathBuf,
+        #[arg(short, long, help = "Sanitized HAR file to write")]
+        output: PathBuf,
+        #[arg(long, value_name = "NAME", help = "Also mask this header (repeatable; `*` for all)")]
+        header: Vec<String>,
+        #[arg(long, value_name = "NAME", help = "Also mask this cookie (repeatable; `*` for all)")]
+        cookie: Vec<String>,
+        #[arg(long, value_name = "NAME", help = "Also mask this query parameter or form field (repeatable)")]
+        query: Vec<String>,
+        #[arg(long, value_name = "KEY", help = "Also mask the value of this JSON key at any depth (repeatable)")]
+        json_key: Vec<String>,
+        #[arg(long, help = "Start from empty lists instead of the built-in defaults")]
+        no_defaults: bool,
+        #[arg(long, help = "Replace values with a short keyed hash so equal values stay correlated")]
+        hash: bool,
+        #[arg(
+            long,
+            value_name = "KEY",
+            help = "Key for --hash, to correlate values across runs (implies --hash; default: random per run)"
+        )]
+        hash_key: Option<String>,
+    },
+    /// Scan a capture for secrets; exits with status 2 when anything is found
+    Scan {
+        #[arg(help = "HAR file to scan")]
+        path: PathBuf,
+    },
+    /// Split a capture into one HAR per page, per host, or per size limit
+    Split {
+        #[arg(help = "HAR file to split")]
+        path: PathBuf,
+        #[arg(long, value_enum, help = "How to divide the entries")]
+        by: har_merge::SplitBy,
+        #[arg(long, default_value_t = 10.0, help = "Size limit of each part in MiB, with --by size")]
+        max_mb: f64,
+        #[arg(short, long, help = "Directory for the parts [default: next to the input file]")]
+        output_dir: Option<PathBuf>,
+    },
+    /// Infer an OpenAPI 3 specification from the API calls in a capture
+    Openapi {
+        #[arg(help = "HAR file to infer the API from")]
+        path: PathBuf,
+        #[arg(
+            short,
+            long,
+            help = "File to write the specification to; YAML if it ends in .yaml or .yml, otherwise JSON (default: JSON on standard output)"
+        )]
+        output: Option<PathBuf>,
+        #[arg(long, help = "Only document requests to this host, e.g. api.example.com")]
+        host: Option<String>,
+    },
+}
+
+/// Exit status of `harview scan` when it finds secrets, so CI can tell findings
+/// from failures, which exit with 1.
+const SCAN_FINDINGS_EXIT: u8 = 2;
+
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
+async fn main() -> anyhow::Result<ExitCode> {
     let args = Args::parse();
-    let har = Har::from_file(args.path.as_path())
+    match args.command {
+        Some(Commands::Diff {
+            baseline,
+            current,
+            slower_pct,
+            slower_ms,
+            exit_code,
+            tui,
+            view,
+        }) => {
+            let options = har_diff::DiffOptions {
+                slower_pct,
+                slower_ms,
+            };
+            let baseline_har = load_har(&baseline).await?;
+            let current_har = load_har(&current).await?;
+            if tui {
+                let name = baseline.display().to_string();
+                let baseline = har_diff::Baseline::new(name, baseline_har, &current_har, options);
+                let mut app = app::App::init(current_har);
+                app.baseline = Some(baseline);
+                app.source_path = Some(current);
+                app.tabbar_state = app::TabBarState::Diff;
+                return view_app(&mut app, &view).await.map(|()| ExitCode::SUCCESS);
+            }
+            let report = har_diff::compare(&baseline_har, &current_har, options);
+            print!(
+                "{}",
+                report.render(
+                    &baseline.display().to_string(),
+                    &current.display().to_string(),
+                    options
+                )
+            );
+            if exit_code && !report.is_empty() {
+                return Ok(ExitCode::FAILURE);
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Commands::Serve {
+            path,
+            host,
+            port,
+            ignore_query,
+            match_headers,
+            ignore_header,
+        }) => {
+            let options = mock_server::MatchOptions {
+                ignore_query,
+                match_headers,
+                ignore_headers: ignore_header,
+            };
+            let server = std::sync::Arc::new(mock_server::MockServer::new(load_har(&path).await?, options));
+            let listener = tokio::net::TcpListener::bind((host.as_str(), port))
+                .await
+                .with_context(|| format!("failed to listen on {}:{}", host, port))?;
+            println!(
+                "Serving {} recorded responses from {} on http://{} (Ctrl-C to stop)",
+                server.entry_count(),
+                path.display(),
+                listener.local_addr()?
+            );
+            tokio::select! {
+                result = mock_server::serve(server.clone(), listener) => result?,
+                _ = tokio::signal::ctrl_c() => {}
+            }
+            let unmatched = server.unmatched();
+            if !unmatched.is_empty() {
+                println!("\n{} unmatched request(s):", unmatched.len());
+                for (request, count) in unmatched {
+                    println!("  {:>4}× {}", count, request);
+                }
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Commands::Record {
+            listen,
+            output,
+            tui,
+            view,
+        }) => {
+            let listener = tokio::net::TcpListener::bind(listen)
+                .await
+                .with_context(|| format!("failed to listen on {}", listen))?;
+            let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+            let mut recorder = recorder::Recorder::new(tui.then_some(tx));
+            recorder.verbose = !tui;
+            let recorder = std::sync::Arc::new(recorder);
+            let proxy = tokio::spawn(recorder::run(recorder.clone(), listener));
+            if tui {
+                let mut app = app::App::init(Har::new(Vec::new()));
+                app.live_entries = Some(rx);
+                app.live_label = "recording";
+                app.source_path = Some(output.clone());
+                view_app(&mut app, &view).await?;
+            } else {
+                println!(
+                    "Recording on http://{} (set HTTP_PROXY to it; Ctrl-C stops and writes {})",
+                    listen,
+                    output.display()
+                );
+                tokio::signal::ctrl_c().await?;
+            }
+            proxy.abort();
+            let har = recorder.har();
+            har.to_file(&output)
+                .with_context(|| format!("failed to write {}", output.display()))?;
+            println!("Wrote {} entries to {}", har.log.entries.len(), output.display());
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Commands::Merge { paths, output }) => {
+            let mut captures = Vec::new();
+            for path in &paths {
+                captures.push(load_har(path).await?);
+            }
+            let har = har_merge::merge(captures);
+            har.to_file(&output)
+                .with_context(|| format!("failed to write {}", output.display()))?;
+            println!(
+                "Merged {} entries from {} files into {}",
+                har.log.entries.len(),
+                paths.len(),
+                output.display()
+            );
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Commands::Redact {
+            path,
+            output,
+            header,
+            cookie,
+            query,
+            json_key,
+            no_defaults,
+            hash,
+            hash_key,
+        }) => {
+            let hash_key = match hash_key {
+                Some(key) => Some(key.into_bytes()),
+                None if hash => Some(redact::random_hash_key()?),
+                None => None,
+            };
+            let mut options = if no_defaults {
+                redact::RedactOptions {
+                    headers: Vec::new(),
+                    cookies: Vec::new(),
+                    query: Vec::new(),
+                    json_keys: Vec::new(),
+                    hash_key,
+                }
+            } else {
+                redact::RedactOptions {
+                    hash_key,
+                    ..redact::RedactOptions::default()
+                }
+            };
+            options.headers.extend(header);
+            options.cookies.extend(cookie);
+            options.query.extend(query);
+            options.json_keys.extend(json_key);
+
+            let (har, report) = redact::redact(&load_har(&path).await?, &options);
+            har.to_file(&output)
+                .with_context(|| format!("failed to write {}", output.display()))?;
+            if report.is_empty() {
+                println!("Nothing matched the redaction rules");
+            } else {
+                print!("{}", report.render());
+            }
+            println!("Wrote {}", output.display());
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Commands::Scan { path }) => {
+            let har = load_har(&path).await?;
+            let findings = secret_scan::scan(&har);
+            for row in secret_scan::format_rows(&findings) {
+                println!("{}", row);
+            }
+            println!("{}", secret_scan::summary(&findings, har.log.entries.len()));
+            if !findings.is_empty() {
+                return Ok(ExitCode::from(SCAN_FINDINGS_EXIT));
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Commands::Split {
+            path,
+            by,
+            max_mb,
+            output_dir,
+        }) => {
+            if by == har_merge::SplitBy::Size && max_mb <= 0.0 {
+                anyhow::bail!("--max-mb must be positive");
+            }
+            let har = load_har(&path).await?;
+            let dir = match output_dir {
+                Some(dir) => {
+                    std::fs::create_dir_all(&dir)
+                        .with_context(|| format!("failed to create {}", dir.display()))?;
+                    dir
+                }
+                None => path.parent().map(PathBuf::from).unwrap_or_default(),
+            };
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            let max_bytes = (max_mb * 1024.0 * 1024.0) as usize;
+            for (label, part) in har_merge::split(&har, by, max_bytes) {
+                let out = dir.join(format!("{}-{}.har", stem, label));
+                part.to_file_compact(&out)
+                    .with_context(|| format!("failed to write {}", out.display()))?;
+                let size = std::fs::metadata(&out).map(|m| m.len()).unwrap_or(0);
+                let over = if by == har_merge::SplitBy::Size && size as usize > max_bytes {
+                    " (a single entry exceeds --max-mb)"
+                } else {
+                    ""
+                };
+                println!(
+                    "{}  {} entries, {:.2} MiB{}",
+                    out.display(),
+                    part.log.entries.len(),
+                    size as f64 / (1024.0 * 1024.0),
+                    over
+                );
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Commands::Openapi { path, output, host }) => {
+            let har = load_har(&path).await?;
+            let source = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string());
+            let spec = openapi::generate(&har, &source, host.as_deref());
+            if spec.requests == 0 {
+                anyhow::bail!("no API requests (JSON, form or write requests) found in {}", path.display());
+            }
+            let yaml = output.as_ref().is_some_and(|output| {
+                output
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml"))
+            });
+            let text = openapi::serialize(&spec.document, yaml)?;
+            let summary = format!(
+                "{} paths, {} operations from {} requests",
+                spec.paths, spec.operations, spec.requests
+            );
+            let Some(output) = output else {
+                // The summary goes to stderr so the document can be piped.
+                print!("{}", text);
+                eprintln!("{}", summary);
+                return Ok(ExitCode::SUCCESS);
+            };
+            std::fs::write(&output, text).with_context(|| format!("failed to write {}", output.display()))?;
+            println!("Wrote {}: {}", output.display(), summary);
+            Ok(ExitCode::SUCCESS)
+        }
+        None => {
+            let mut captures = Vec::new();
+            for path in &args.path {
+                captures.push(load_har(path).await?);
+            }
+            let mut app = if captures.len() == 1 {
+                let mut app = app::App::init(captures.remove(0));
+                app.source_path = args.path.first().cloned();
+                app
+            } else {
+                let (har, entry_sources) = har_merge::timeline(captures);
+                app::App::init(har).with_sources(har_merge::source_names(&args.path), entry_sources)
+            };
+            if args.follow {
+                let [path] = <[PathBuf; 1]>::try_from(args.path)
+                    .map_err(|_| anyhow::anyhow!("--follow works with a single file"))?;
+                let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+                let known = app.har.log.entries.len();
+                tokio::spawn(follow::watch(path, known, tx, follow::POLL_INTERVAL));
+                app.live_entries = Some(rx);
+                app.live_label = "following";
+            }
+            view_app(&mut app, &args.view).await.map(|()| ExitCode::SUCCESS)
+        }
+    }
+}
+
+async fn load_har(path: &std::path::Path) -> anyhow::Result<Har> {
+    Har::from_file(path)
         .await
-        .context("failed to parse HAR file")?;
-    let mut app = app::App::init(har);
-    run(&mut app).await?;
+        .with_context(|| format!("failed to parse HAR file {}", path.display()))
+}
 
-    Ok(())
+async fn view_app(app: &mut app::App, view: &ViewArgs) -> anyhow::Result<()> {
+    app.image_protocol = view.image_protocol.detect();
+    app.replay_base = view.replay_to.clone();
+    run(app).await
 }
 
 pub async fn run(app: &mut app::App) -> anyhow::Result<()> {
@@ -54,6 +504,7 @@ pub async fn run(app: &mut app::App) -> anyhow::Result<()> {
         let needs_draw = match tui.events.next().await? {
             event::Event::Tick => false, // Ticks don't change state, skip redraw
             event::Event::Key(key_event) => {
+                app.status_message = None;
                 if let Some(command) = handler::handle_key_events(key_event, app) {
                     command.exec(app);
                 }
@@ -72,6 +523,8 @@ pub async fn run(app: &mut app::App) -> anyhow::Result<()> {
             }
         };
 
+        let needs_draw = app.poll_live_entries() | app.poll_replay() || needs_draw;
+
         if !app.running {
             break;
         }
@@ -86,6 +539,7 @@ pub async fn run(app: &mut app::App) -> anyhow::Result<()> {
                 app::PendingAction::OpenInBat => app.open_in_bat(),
                 app::PendingAction::OpenInFx => app.open_in_fx(),
                 app::PendingAction::OpenInEditor => app.open_in_editor(),
+                app::PendingAction::EditReplay => app.edit_and_replay(),
             };
             if let Err(e) = result {
                 eprintln!("Error: {}", e);
@@ -0,0 +1,332 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::header::{HeaderName, HeaderValue};
+use hyper::service::service_fn;
+use hyper::{Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+
+use crate::app;
+use crate::har::{self, Har};
+
+/// Headers that describe the recorded connection or encoding rather than the
+/// response itself; the served body is already decoded and sized by hyper.
+const SKIPPED_RESPONSE_HEADERS: &[&str] = &[
+    "connection",
+    "content-encoding",
+    "content-length",
+    "keep-alive",
+    "transfer-encoding",
+];
+
+/// Request headers never compared when matching on headers: they depend on the
+/// client and the address the mock server listens on.
+const SKIPPED_REQUEST_HEADERS: &[&str] = &["connection", "content-length", "host"];
+
+/// How closely an incoming request must resemble a recorded one.
+#[derive(Debug, Clone, Default)]
+pub struct MatchOptions {
+    /// Query parameters left out of the comparison; `*` ignores the whole query.
+    pub ignore_query: Vec<String>,
+    /// Also require every recorded request header to be sent with the same value.
+    pub match_headers: bool,
+    /// Headers exempt from `match_headers`.
+    pub ignore_headers: Vec<String>,
+}
+
+impl MatchOptions {
+    fn ignores_param(&self, name: &str) -> bool {
+        self.ignore_query.iter().any(|p| p == "*" || p == name)
+    }
+
+    fn ignores_header(&self, name: &str) -> bool {
+        name.starts_with(':')
+            || SKIPPED_REQUEST_HEADERS.iter().any(|h| h.eq_ignore_ascii_case(name))
+            || self.ignore_headers.iter().any(|h| h.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Method, path and sorted query, minus ignored parameters.
+fn route_key(method: &str, path: &str, query: Option<&str>, options: &MatchOptions) -> String {
+    let mut params: Vec<(String, String)> = url::form_urlencoded::parse(query.unwrap_or_default().as_bytes())
+        .into_owned()
+        .filter(|(name, _)| !options.ignores_param(name))
+        .collect();
+    params.sort();
+    let mut key = format!("{} {}", method.to_uppercase(), path);
+    if !params.is_empty() {
+        let joined: Vec<String> = params.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        key.push('?');
+        key.push_str(&joined.join("&"));
+    }
+    key
+}
+
+/// Recorded responses indexed by route, with per-route replay position.
+pub struct MockServer {
+    har: Har,
+    options: MatchOptions,
+    routes: HashMap<String, Vec<usize>>,
+    /// How many times each route has been answered, for sequence-aware replay.
+    served: Mutex<HashMap<String, usize>>,
+    /// Requests that matched nothing, with how often each was seen.
+    unmatched: Mutex<Vec<(String, usize)>>,
+}
+
+impl MockServer {
+    pub fn new(har: Har, options: MatchOptions) -> Self {
+        let mut routes: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, entry) in har.log.entries.iter().enumerate() {
+            let url = &entry.request.url;
+            routes
+                .entry(route_key(&entry.request.method, url.path(), url.query(), &options))
+                .or_default()
+                .push(i);
+        }
+        Self {
+            har,
+            options,
+            routes,
+            served: Mutex::new(HashMap::new()),
+            unmatched: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn entry_count(&self) -> usize {
+        self.har.log.entries.len()
+    }
+
+    /// Entry answering a request. Identical requests replay the recorded
+    /// entries in order; once they run out the last one keeps being served.
+    pub fn lookup(&self, method: &str, path: &str, query: Option<&str>, headers: &[(String, String)]) -> Option<usize> {
+        let key = route_key(method, path, query, &self.options);
+        let candidates: Vec<usize> = self
+            .routes
+            .get(&key)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|&i| self.headers_match(&self.har.log.entries[i].request, headers))
+            .collect();
+        if candidates.is_empty() {
+            let mut unmatched = self.unmatched.lock().unwrap();
+            match unmatched.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, count)) => *count += 1,
+                None => unmatched.push((key, 1)),
+            }
+            return None;
+        }
+        let mut served = self.served.lock().unwrap();
+        let count = served.entry(key).or_insert(0);
+        let index = candidates[(*count).min(candidates.len() - 1)];
+        *count += 1;
+        Some(index)
+    }
+
+    fn headers_match(&self, recorded: &har::Request, sent: &[(String, String)]) -> bool {
+        !self.options.match_headers
+            || recorded
+                .headers
+                .iter()
+                .filter(|h| !self.options.ignores_header(&h.name))
+                .all(|h| sent.iter().any(|(n, v)| n.eq_ignore_ascii_case(&h.name) && *v == h.value))
+    }
+
+    /// Unmatched requests in order of first occurrence, with their counts.
+    pub fn unmatched(&self) -> Vec<(String, usize)> {
+        self.unmatched.lock().unwrap().clone()
+    }
+
+    fn handle<B>(&self, req: &Request<B>) -> Response<Full<Bytes>> {
+        let headers: Vec<(String, String)> = req
+            .headers()
+            .iter()
+            .map(|(n, v)| (n.as_str().to_string(), String::from_utf8_lossy(v.as_bytes()).into_owned()))
+            .collect();
+        let uri = req.uri();
+        let target = uri.path_and_query().map_or("/", |pq| pq.as_str());
+        match self.lookup(req.method().as_str(), uri.path(), uri.query(), &headers) {
+            Some(index) => {
+                let response = recorded_response(&self.har.log.entries[index]);
+                println!("{} {} {} ← #{}", response.status().as_u16(), req.method(), target, index + 1);
+                response
+            }
+            None => {
+                println!("404 {} {} (unmatched)", req.method(), target);
+                let mut response = Response::new(Full::new(Bytes::from(format!(
+                    "harview: no recorded response for {} {}\n",
+                    req.method(),
+                    target
+                ))));
+                *response.status_mut() = StatusCode::NOT_FOUND;
+                response
+            }
+        }
+    }
+}
+
+/// The recorded response with its body decoded. Entries without a status
+/// (blocked or failed requests) are answered with 502.
+fn recorded_response(entry: &har::Entry) -> Response<Full<Bytes>> {
+    let body = app::decode_response(entry).map(|d| d.bytes).unwrap_or_default();
+    let mut response = Response::new(Full::new(Bytes::from(body)));
+    *response.status_mut() = u16::try_from(entry.response.status)
+        .ok()
+        .and_then(|s| StatusCode::from_u16(s).ok())
+        .unwrap_or(StatusCode::BAD_GATEWAY);
+    let headers = response.headers_mut();
+    for header in &entry.response.headers {
+        if header.name.starts_with(':')
+            || SKIPPED_RESPONSE_HEADERS.iter().any(|h| h.eq_ignore_ascii_case(&header.name))
+        {
+            continue;
+        }
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(header.name.as_bytes()),
+            HeaderValue::from_str(&header.value),
+        ) {
+            headers.append(name, value);
+        }
+    }
+    response
+}
+
+/// Answer connections on `listener` until the task is dropped.
+pub async fn serve(server: Arc<MockServer>, listener: TcpListener) -> Result<()> {
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let server = server.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |req| {
+                let response = server.handle(&req);
+                async move { Ok::<_, Infallible>(response) }
+            });
+            if let Err(e) = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                eprintln!("connection error: {}", e);
+            }
+        });
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn entry(method: &str, url: &str, status: i64, body: &str) -> har::Entry {
+        har::Entry::test(method, url, status)
+            .with_request_headers(&[("X-Tenant", "acme"), ("Host", "x.test")])
+            .with_response_headers(&[("Content-Type", "application/json"), ("Content-Length", "999")])
+            .with_response_body("application/json", body)
+    }
+
+    fn server(entries: Vec<har::Entry>, options: MatchOptions) -> MockServer {
+        MockServer::new(Har::new(entries), options)
+    }
+
+    #[test]
+    fn matches_method_path_and_query_in_any_order() {
+        let s = server(
+            vec![
+                entry("GET", "https://x.test/items?a=1&b=2", 200, "[]"),
+                entry("POST", "https://x.test/items", 201, "{}"),
+            ],
+            MatchOptions::default(),
+        );
+        assert_eq!(s.lookup("GET", "/items", Some("b=2&a=1"), &[]), Some(0));
+        assert_eq!(s.lookup("post", "/items", None, &[]), Some(1));
+        assert_eq!(s.lookup("GET", "/items", Some("a=1"), &[]), None);
+        assert_eq!(s.lookup("GET", "/items", Some("a=1"), &[]), None);
+        assert_eq!(s.unmatched(), vec![("GET /items?a=1".to_string(), 2)]);
+    }
+
+    #[test]
+    fn ignored_query_params() {
+        let recorded = vec![entry("GET", "https://x.test/items?page=2&_=1717", 200, "[]")];
+        let options = MatchOptions {
+            ignore_query: vec!["_".to_string()],
+            ..Default::default()
+        };
+        let s = server(recorded.clone(), options);
+        assert_eq!(s.lookup("GET", "/items", Some("page=2&_=9999"), &[]), Some(0));
+        assert_eq!(s.lookup("GET", "/items", Some("page=3"), &[]), None);
+
+        let options = MatchOptions {
+            ignore_query: vec!["*".to_string()],
+            ..Default::default()
+        };
+        let s = server(recorded, options);
+        assert_eq!(s.lookup("GET", "/items", Some("page=3"), &[]), Some(0));
+    }
+
+    #[test]
+    fn repeated_requests_replay_in_sequence() {
+        let s = server(
+            vec![
+                entry("GET", "https://x.test/job", 202, r#"{"state":"running"}"#),
+                entry("GET", "https://x.test/other", 200, "{}"),
+                entry("GET", "https://x.test/job", 200, r#"{"state":"done"}"#),
+            ],
+            MatchOptions::default(),
+        );
+        assert_eq!(s.lookup("GET", "/job", None, &[]), Some(0));
+        assert_eq!(s.lookup("GET", "/job", None, &[]), Some(2));
+        assert_eq!(s.lookup("GET", "/job", None, &[]), Some(2));
+    }
+
+    #[test]
+    fn header_matching_respects_ignored_headers() {
+        let options = MatchOptions {
+            match_headers: true,
+            ..Default::default()
+        };
+        let s = server(vec![entry("GET", "https://x.test/me", 200, "{}")], options);
+        let tenant = |v: &str| vec![("x-tenant".to_string(), v.to_string())];
+        assert_eq!(s.lookup("GET", "/me", None, &tenant("acme")), Some(0));
+        assert_eq!(s.lookup("GET", "/me", None, &tenant("other")), None);
+
+        let options = MatchOptions {
+            match_headers: true,
+            ignore_headers: vec!["X-Tenant".to_string()],
+            ..Default::default()
+        };
+        let s = server(vec![entry("GET", "https://x.test/me", 200, "{}")], options);
+        assert_eq!(s.lookup("GET", "/me", None, &tenant("other")), Some(0));
+    }
+
+    #[tokio::test]
+    async fn serves_recorded_response_over_http() {
+        let s = Arc::new(server(
+            vec![entry("GET", "https://x.test/items", 200, r#"{"ok":true}"#)],
+            MatchOptions::default(),
+        ));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let task = tokio::spawn(serve(s, listener));
+
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET /items HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut raw = String::new();
+        stream.read_to_string(&mut raw).await.unwrap();
+        task.abort();
+
+        assert!(raw.starts_with("HTTP/1.1 200 OK\r\n"), "{}", raw);
+        assert!(raw.contains("content-type: application/json\r\n"));
+        // The recorded Content-Length is replaced by the real one.
+        assert!(raw.contains("content-length: 11\r\n"));
+        assert!(raw.ends_with(r#"{"ok":true}"#));
+    }
+}
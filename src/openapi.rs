@@ -0,0 +1,658 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::{Map, Value};
+
+use crate::app;
+use crate::endpoints;
+use crate::har::{self, Har};
+
+/// OpenAPI version written; 3.0 is what most tooling reads.
+const OPENAPI_VERSION: &str = "3.0.3";
+
+/// Operations in the order OpenAPI lists them in a path item.
+const METHODS: &[&str] = &["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+// ── Schema inference ──────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Kind {
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Null => "null",
+            Kind::Boolean => "boolean",
+            Kind::Integer => "integer",
+            Kind::Number => "number",
+            Kind::String => "string",
+            Kind::Array => "array",
+            Kind::Object => "object",
+        }
+    }
+}
+
+/// JSON schema of every sample seen so far: the union of their types, with
+/// object properties required only when every sample had them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Schema {
+    kinds: BTreeSet<Kind>,
+    properties: BTreeMap<String, Schema>,
+    /// Keys present in every object sample; `None` before the first object.
+    required: Option<BTreeSet<String>>,
+    items: Option<Box<Schema>>,
+    /// Format shared by every string sample; `Some(None)` once they disagree.
+    format: Option<Option<&'static str>>,
+}
+
+impl Schema {
+    /// Merge another sample into the schema.
+    pub fn add(&mut self, value: &serde_json::Value) {
+        use serde_json::Value as Json;
+        match value {
+            Json::Null => {
+                self.kinds.insert(Kind::Null);
+            }
+            Json::Bool(_) => {
+                self.kinds.insert(Kind::Boolean);
+            }
+            Json::Number(n) => {
+                self.kinds.insert(if n.is_f64() { Kind::Number } else { Kind::Integer });
+            }
+            Json::String(s) => {
+                self.kinds.insert(Kind::String);
+                let format = string_format(s);
+                self.format = Some(match self.format {
+                    None => format,
+                    Some(previous) if previous == format => format,
+                    Some(_) => None,
+                });
+            }
+            Json::Array(items) => {
+                self.kinds.insert(Kind::Array);
+                let schema = self.items.get_or_insert_with(Default::default);
+                for item in items {
+                    schema.add(item);
+                }
+            }
+            Json::Object(map) => {
+                self.kinds.insert(Kind::Object);
+                let keys: BTreeSet<String> = map.keys().cloned().collect();
+                self.required = Some(match self.required.take() {
+                    None => keys,
+                    Some(required) => required.intersection(&keys).cloned().collect(),
+                });
+                for (key, value) in map {
+                    self.properties.entry(key.clone()).or_default().add(value);
+                }
+            }
+        }
+    }
+
+    /// OpenAPI 3.0 schema object: `nullable` for nulls, `oneOf` for mixed types.
+    pub fn to_value(&self) -> Value {
+        let mut kinds = self.kinds.clone();
+        let nullable = kinds.remove(&Kind::Null);
+        if kinds.contains(&Kind::Number) {
+            kinds.remove(&Kind::Integer);
+        }
+        let mut variants: Vec<Map<String, Value>> = kinds.iter().map(|&kind| self.kind_schema(kind)).collect();
+        let mut schema = match variants.len() {
+            0 => Map::new(),
+            1 => variants.remove(0),
+            _ => map([("oneOf", Value::Array(variants.into_iter().map(Value::Object).collect()))]),
+        };
+        if nullable {
+            schema.insert("nullable".into(), true.into());
+        }
+        Value::Object(schema)
+    }
+
+    fn kind_schema(&self, kind: Kind) -> Map<String, Value> {
+        let mut schema = map([("type", kind.name().into())]);
+        match kind {
+            Kind::String => {
+                if let Some(Some(format)) = self.format {
+                    schema.insert("format".into(), format.into());
+                }
+            }
+            Kind::Array => {
+                let items = self.items.as_ref().map_or(Value::Object(Map::new()), |s| s.to_value());
+                schema.insert("items".into(), items);
+            }
+            Kind::Object => {
+                if !self.properties.is_empty() {
+                    let properties = self
+                        .properties
+                        .iter()
+                        .map(|(name, schema)| (name.as_str().into(), schema.to_value()))
+                        .collect();
+                    schema.insert("properties".into(), Value::Object(properties));
+                }
+                if let Some(required) = self.required.as_ref().filter(|r| !r.is_empty()) {
+                    let names = required.iter().map(|name| name.as_str().into()).collect();
+                    schema.insert("required".into(), Value::Array(names));
+                }
+            }
+            _ => {}
+        }
+        schema
+    }
+}
+
+/// `date-time`, `date`, `uuid` or `uri` when the string clearly is one.
+fn string_format(s: &str) -> Option<&'static str> {
+    if chrono::DateTime::parse_from_rfc3339(s).is_ok() {
+        Some("date-time")
+    } else if s.len() == 10 && chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok() {
+        Some("date")
+    } else if is_uuid(s) {
+        Some("uuid")
+    } else if (s.starts_with("https://") || s.starts_with("http://")) && url::Url::parse(s).is_ok() {
+        Some("uri")
+    } else {
+        None
+    }
+}
+
+fn is_uuid(s: &str) -> bool {
+    s.len() == 36
+        && s.char_indices().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+/// A query, form or path value as the JSON scalar it looks like.
+fn scalar(value: &str) -> serde_json::Value {
+    if let Ok(n) = value.parse::<i64>() {
+        return n.into();
+    }
+    if let Some(n) = value.parse::<f64>().ok().and_then(serde_json::Number::from_f64) {
+        return serde_json::Value::Number(n);
+    }
+    match value {
+        "true" => true.into(),
+        "false" => false.into(),
+        _ => value.into(),
+    }
+}
+
+fn map<const N: usize>(pairs: [(&str, Value); N]) -> Map<String, Value> {
+    pairs.into_iter().map(|(key, value)| (key.into(), value)).collect()
+}
+
+// ── Paths ─────────────────────────────────────────────────────────────────────
+
+/// Parameter name for an id following `segment`: `users` → `userId`.
+fn param_name(segment: Option<&str>) -> String {
+    let Some(segment) = segment.filter(|s| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphabetic() || c == '-' || c == '_')) else {
+        return "id".to_string();
+    };
+    let singular = if let Some(stem) = segment.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else if segment.ends_with('s') && !segment.ends_with("ss") {
+        segment[..segment.len() - 1].to_string()
+    } else {
+        segment.to_string()
+    };
+    let mut name = String::new();
+    let mut upper = false;
+    for c in singular.chars() {
+        if c == '-' || c == '_' {
+            upper = true;
+        } else if upper {
+            name.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            name.push(c);
+        }
+    }
+    format!("{}Id", name)
+}
+
+/// OpenAPI path of `url` with ids replaced by named parameters, and each
+/// parameter's name and value in this URL.
+pub fn path_template(url: &url::Url) -> (String, Vec<(String, String)>) {
+    let mut params: Vec<(String, String)> = Vec::new();
+    let mut segments: Vec<String> = Vec::new();
+    for segment in url.path().split('/').skip(1) {
+        if !endpoints::is_id(segment) {
+            segments.push(segment.to_string());
+            continue;
+        }
+        let previous = segments.last().filter(|s| !s.starts_with('{')).map(String::as_str);
+        let base = param_name(previous);
+        let mut name = base.clone();
+        let mut n = 2;
+        while params.iter().any(|(existing, _)| *existing == name) {
+            name = format!("{}{}", base, n);
+            n += 1;
+        }
+        segments.push(format!("{{{}}}", name));
+        params.push((name, segment.to_string()));
+    }
+    (format!("/{}", segments.join("/")), params)
+}
+
+// ── Operations ────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Default)]
+struct Operation {
+    samples: usize,
+    path_params: Vec<(String, Schema)>,
+    /// Samples that had the parameter, and its schema.
+    query: BTreeMap<String, (usize, Schema)>,
+    bodies: usize,
+    request_content: BTreeMap<String, Schema>,
+    responses: BTreeMap<i64, Response>,
+}
+
+#[derive(Debug, Default)]
+struct Response {
+    description: String,
+    content: BTreeMap<String, Schema>,
+}
+
+/// Media type without parameters, e.g. `application/json`.
+fn media_type(mime: &str) -> String {
+    mime.split(';').next().unwrap_or_default().trim().to_lowercase()
+}
+
+fn is_json(media_type: &str) -> bool {
+    media_type.ends_with("/json") || media_type.ends_with("+json")
+}
+
+/// Whether the entry looks like an API call rather than a page or asset load.
+pub fn is_api(entry: &har::Entry) -> bool {
+    let method = entry.request.method.to_uppercase();
+    if method == "OPTIONS" || entry.response.status == 0 {
+        return false;
+    }
+    let response = media_type(entry.response.content.mime_type.as_deref().unwrap_or_default());
+    let request = entry.request.post_data.as_ref().map(|p| media_type(&p.mime_type));
+    is_json(&response)
+        || request.is_some_and(|m| is_json(&m) || m == "application/x-www-form-urlencoded")
+        || (matches!(method.as_str(), "POST" | "PUT" | "PATCH" | "DELETE") && !response.starts_with("text/html"))
+}
+
+/// Add a body sample of `media_type`: JSON and form fields are inferred
+/// field by field, anything else is a string (binary for images, fonts and
+/// other non-text types). JSON and form bodies whose text the capture left
+/// out add no sample, so the media type is listed without guessing a schema.
+fn add_body(schema: &mut Schema, media_type: &str, text: Option<&str>) {
+    let structured = is_json(media_type) || media_type == "application/x-www-form-urlencoded";
+    let text = text.filter(|t| !t.is_empty());
+    if structured && text.is_none() {
+        return;
+    }
+    if is_json(media_type) {
+        if let Some(json) = text.and_then(|t| serde_json::from_str::<serde_json::Value>(t).ok()) {
+            schema.add(&json);
+            return;
+        }
+    }
+    if media_type == "application/x-www-form-urlencoded" {
+        let fields: serde_json::Map<String, serde_json::Value> = url::form_urlencoded::parse(text.unwrap_or_default().as_bytes())
+            .map(|(name, value)| (name.into_owned(), scalar(&value)))
+            .collect();
+        schema.add(&serde_json::Value::Object(fields));
+        return;
+    }
+    schema.add(&serde_json::Value::String(String::new()));
+    let textual = media_type.starts_with("text/")
+        || media_type.ends_with("xml")
+        || media_type.ends_with("javascript")
+        || media_type.is_empty();
+    if !textual {
+        schema.format = Some(Some("binary"));
+    }
+}
+
+impl Operation {
+    fn add(&mut self, entry: &har::Entry, path_params: &[(String, String)]) {
+        self.samples += 1;
+        for (i, (name, value)) in path_params.iter().enumerate() {
+            if self.path_params.len() <= i {
+                self.path_params.push((name.clone(), Schema::default()));
+            }
+            self.path_params[i].1.add(&scalar(value));
+        }
+
+        let mut seen = BTreeSet::new();
+        for (name, value) in entry.request.url.query_pairs() {
+            let (count, schema) = self.query.entry(name.to_string()).or_default();
+            if seen.insert(name.to_string()) {
+                *count += 1;
+            }
+            schema.add(&scalar(&value));
+        }
+
+        if let Some(post_data) = entry.request.post_data.as_ref().filter(|p| !p.text.is_empty()) {
+            self.bodies += 1;
+            let media_type = media_type(&post_data.mime_type);
+            add_body(self.request_content.entry(media_type.clone()).or_default(), &media_type, Some(&post_data.text));
+        }
+
+        let response = &entry.response;
+        let slot = self.responses.entry(response.status).or_insert_with(|| Response {
+            description: description(response),
+            content: BTreeMap::new(),
+        });
+        let has_body = response.content.size.unwrap_or(0) > 0 || response.content.text.as_deref().is_some_and(|t| !t.is_empty());
+        if has_body && !matches!(response.status, 204 | 304) {
+            let media_type = media_type(response.content.mime_type.as_deref().unwrap_or_default());
+            let media_type = if media_type.is_empty() {
+                "application/octet-stream".to_string()
+            } else {
+                media_type
+            };
+            let text = if is_json(&media_type) { app::response_text(entry) } else { None };
+            add_body(slot.content.entry(media_type.clone()).or_default(), &media_type, text.as_deref());
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        let mut operation = Map::new();
+        let mut parameters: Vec<Value> = self
+            .path_params
+            .iter()
+            .map(|(name, schema)| {
+                Value::Object(map([
+                    ("name", name.as_str().into()),
+                    ("in", "path".into()),
+                    ("required", true.into()),
+                    ("schema", schema.to_value()),
+                ]))
+            })
+            .collect();
+        parameters.extend(self.query.iter().map(|(name, (count, schema))| {
+            Value::Object(map([
+                ("name", name.as_str().into()),
+                ("in", "query".into()),
+                ("required", (*count == self.samples).into()),
+                ("schema", schema.to_value()),
+            ]))
+        }));
+        if !parameters.is_empty() {
+            operation.insert("parameters".into(), Value::Array(parameters));
+        }
+        if self.bodies > 0 {
+            operation.insert(
+                "requestBody".into(),
+                Value::Object(map([
+                    ("required", (self.bodies == self.samples).into()),
+                    ("content", content(&self.request_content)),
+                ])),
+            );
+        }
+        let responses = self
+            .responses
+            .iter()
+            .map(|(status, response)| {
+                let mut value = map([("description", response.description.as_str().into())]);
+                if !response.content.is_empty() {
+                    value.insert("content".into(), content(&response.content));
+                }
+                (status.to_string(), Value::Object(value))
+            })
+            .collect();
+        operation.insert("responses".into(), Value::Object(responses));
+        Value::Object(operation)
+    }
+}
+
+fn description(response: &har::Response) -> String {
+    let text = response.status_text.trim();
+    if !text.is_empty() {
+        return text.to_string();
+    }
+    u16::try_from(response.status)
+        .ok()
+        .and_then(|s| hyper::StatusCode::from_u16(s).ok())
+        .and_then(|s| s.canonical_reason())
+        .unwrap_or("Response")
+        .to_string()
+}
+
+fn content(by_type: &BTreeMap<String, Schema>) -> Value {
+    Value::Object(
+        by_type
+            .iter()
+            .map(|(media_type, schema)| {
+                (
+                    media_type.as_str().into(),
+                    Value::Object(map([("schema", schema.to_value())])),
+                )
+            })
+            .collect(),
+    )
+}
+
+// ── Document ──────────────────────────────────────────────────────────────────
+
+/// An inferred OpenAPI document and what went into it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spec {
+    pub document: Value,
+    pub paths: usize,
+    pub operations: usize,
+    /// API requests the document was inferred from.
+    pub requests: usize,
+}
+
+/// Infer an OpenAPI 3 document from the API calls in `har`, optionally only
+/// those to `host`. `source` names the capture in the document's info.
+pub fn generate(har: &Har, source: &str, host: Option<&str>) -> Spec {
+    let mut servers: Vec<String> = Vec::new();
+    let mut paths: BTreeMap<String, (BTreeSet<String>, BTreeMap<String, Operation>)> = BTreeMap::new();
+    let mut requests = 0;
+    for entry in &har.log.entries {
+        let url = &entry.request.url;
+        if !is_api(entry) || host.is_some_and(|h| url.host_str() != Some(h)) {
+            continue;
+        }
+        let method = entry.request.method.to_lowercase();
+        if !METHODS.contains(&method.as_str()) {
+            continue;
+        }
+        requests += 1;
+        let origin = url.origin().ascii_serialization();
+        if !servers.contains(&origin) {
+            servers.push(origin.clone());
+        }
+        let (path, params) = path_template(url);
+        let (origins, operations) = paths.entry(path).or_default();
+        origins.insert(origin);
+        operations.entry(method).or_default().add(entry, &params);
+    }
+
+    let operations = paths.values().map(|(_, ops)| ops.len()).sum();
+    let path_items: Map<String, Value> = paths
+        .iter()
+        .map(|(path, (origins, operations))| {
+            let mut item = Map::new();
+            if servers.len() > 1 {
+                let urls = servers
+                    .iter()
+                    .filter(|s| origins.contains(*s))
+                    .map(|s| Value::Object(map([("url", s.as_str().into())])))
+                    .collect();
+                item.insert("servers".into(), Value::Array(urls));
+            }
+            for method in METHODS {
+                if let Some(operation) = operations.get(*method) {
+                    item.insert((*method).into(), operation.to_value());
+                }
+            }
+            (path.as_str().into(), Value::Object(item))
+        })
+        .collect();
+
+    let info = map([
+        ("title", format!("API inferred from {}", source).into()),
+        (
+            "description",
+            format!("Generated by harview from {} requests captured in {}.", requests, source).into(),
+        ),
+        ("version", "1.0.0".into()),
+    ]);
+    let servers_value = servers
+        .iter()
+        .map(|s| Value::Object(map([("url", s.as_str().into())])))
+        .collect();
+    let document = Value::Object(map([
+        ("openapi", OPENAPI_VERSION.into()),
+        ("info", Value::Object(info)),
+        ("servers", Value::Array(servers_value)),
+        ("paths", Value::Object(path_items)),
+    ]));
+    Spec {
+        document,
+        paths: paths.len(),
+        operations,
+        requests,
+    }
+}
+
+/// The document as YAML when `yaml` is set, otherwise as pretty-printed JSON.
+/// Keys keep the order OpenAPI tools show them in.
+pub fn serialize(document: &Value, yaml: bool) -> anyhow::Result<String> {
+    Ok(if yaml {
+        serde_norway::to_string(document)?
+    } else {
+        serde_json::to_string_pretty(document)? + "\n"
+    })
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(method: &str, url: &str, body: Option<&str>, status: i64, response: Option<&str>) -> har::Entry {
+        let mut entry = har::Entry::test(method, url, status);
+        if let Some(body) = body {
+            entry = entry.with_request_body("application/json", body);
+        }
+        match response {
+            Some(text) => entry.with_response_body("application/json; charset=utf-8", text),
+            None => entry,
+        }
+    }
+
+    #[test]
+    fn schemas_merge_across_samples() {
+        let mut schema = Schema::default();
+        schema.add(&serde_json::json!({"id": 1, "name": "a", "tags": ["x"], "at": "2024-06-01T12:00:00Z"}));
+        schema.add(&serde_json::json!({"id": 2.5, "name": null, "tags": [], "at": "2024-06-01T13:00:00Z"}));
+        schema.add(&serde_json::json!({"id": 3, "name": "c", "extra": true}));
+        let value = schema.to_value();
+        assert_eq!(value["type"], "object");
+        assert_eq!(value["required"], Value::Array(vec!["id".into(), "name".into()]));
+        let properties = &value["properties"];
+        assert_eq!(properties["id"]["type"], "number");
+        assert_eq!(properties["name"]["type"], "string");
+        assert_eq!(properties["name"]["nullable"], true);
+        assert_eq!(properties["tags"]["items"]["type"], "string");
+        assert_eq!(properties["at"]["format"], "date-time");
+        assert_eq!(properties["extra"]["type"], "boolean");
+
+        let mut mixed = Schema::default();
+        mixed.add(&serde_json::json!([1, "a"]));
+        assert_eq!(mixed.to_value()["items"]["oneOf"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn names_path_parameters_after_their_collection() {
+        let url = |s: &str| url::Url::parse(s).unwrap();
+        let (path, params) = path_template(&url("https://x.test/api/categories/7/line-items/123e4567-e89b-12d3-a456-426614174000/99"));
+        assert_eq!(path, "/api/categories/{categoryId}/line-items/{lineItemId}/{id}");
+        assert_eq!(params[1].1, "123e4567-e89b-12d3-a456-426614174000");
+        let (path, _) = path_template(&url("https://x.test/7/8"));
+        assert_eq!(path, "/{id}/{id2}");
+        assert_eq!(path_template(&url("https://x.test/")).0, "/");
+    }
+
+    #[test]
+    fn infers_operations_from_api_traffic() {
+        let mut page = entry("GET", "https://app.test/", None, 200, Some("<html>"));
+        page.response.content.mime_type = Some("text/html".to_string());
+        let har = Har::new(vec![
+            page,
+            entry("GET", "https://api.test/users/1?expand=true&page=2", None, 200, Some(r#"{"id":1,"email":"a@x.test"}"#)),
+            entry("GET", "https://api.test/users/2?page=1", None, 200, Some(r#"{"id":2,"email":null}"#)),
+            entry("GET", "https://api.test/users/3", None, 404, Some(r#"{"error":"not found"}"#)),
+            entry("POST", "https://api.test/users", Some(r#"{"email":"b@x.test"}"#), 201, Some(r#"{"id":4}"#)),
+            entry("DELETE", "https://api.test/users/4", None, 204, None),
+            entry("OPTIONS", "https://api.test/users", None, 204, None),
+        ]);
+        let spec = generate(&har, "capture.har", None);
+        assert_eq!((spec.paths, spec.operations, spec.requests), (2, 3, 5));
+
+        let document = &spec.document;
+        assert_eq!(document["openapi"], "3.0.3");
+        assert_eq!(document["servers"][0]["url"], "https://api.test");
+        let get = &document["paths"]["/users/{userId}"]["get"];
+        let params = get["parameters"].as_array().unwrap();
+        let summary: Vec<_> = params
+            .iter()
+            .map(|p| (p["name"].as_str().unwrap(), p["in"].as_str().unwrap(), p["required"].as_bool().unwrap()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("userId", "path", true), ("expand", "query", false), ("page", "query", false)]
+        );
+        assert_eq!(params[0]["schema"]["type"], "integer");
+        assert_eq!(params[1]["schema"]["type"], "boolean");
+        let ok = &get["responses"]["200"];
+        assert_eq!(ok["description"], "OK");
+        let schema = &ok["content"]["application/json"]["schema"];
+        assert_eq!(schema["properties"]["email"]["nullable"], true);
+        assert_eq!(get["responses"]["404"]["content"]["application/json"]["schema"]["properties"]["error"]["type"], "string");
+
+        let post = &document["paths"]["/users"]["post"];
+        assert_eq!(post["requestBody"]["required"], true);
+        assert_eq!(post["requestBody"]["content"]["application/json"]["schema"]["required"][0], "email");
+        let delete = &document["paths"]["/users/{userId}"]["delete"];
+        assert_eq!(delete["responses"]["204"]["description"], "No Content");
+        assert!(delete["responses"]["204"].get("content").is_none());
+
+        let text = serialize(document, false).unwrap();
+        assert!(
+            text.starts_with("{\n  \"openapi\": \"3.0.3\",\n  \"info\": {\n    \"title\": \"API inferred from capture.har\","),
+            "{}",
+            text
+        );
+        let yaml = serialize(document, true).unwrap();
+        assert!(yaml.starts_with("openapi: 3.0.3\ninfo:\n  title: API inferred from capture.har\n"), "{}", yaml);
+        assert_eq!(serde_norway::from_str::<Value>(&yaml).unwrap(), *document);
+        assert_eq!(generate(&har, "capture.har", Some("other.test")).requests, 0);
+    }
+
+    #[test]
+    fn bodies_left_out_of_the_capture_add_no_sample() {
+        let mut dropped = entry("GET", "https://api.test/items", None, 200, None);
+        dropped.response.content.size = Some(120);
+        let har = Har::new(vec![
+            dropped.clone(),
+            entry("GET", "https://api.test/items", None, 200, Some(r#"{"items":[]}"#)),
+        ]);
+        let document = generate(&har, "capture.har", None).document;
+        let schema = &document["paths"]["/items"]["get"]["responses"]["200"]["content"]["application/json"]["schema"];
+        assert_eq!(schema["type"], "object");
+        assert!(schema.get("oneOf").is_none());
+
+        let document = generate(&Har::new(vec![dropped]), "capture.har", None).document;
+        let content = &document["paths"]["/items"]["get"]["responses"]["200"]["content"];
+        assert_eq!(content["application/json"]["schema"], Value::Object(Map::new()));
+    }
+}
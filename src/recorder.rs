@@ -0,0 +1,614 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use anyhow::{anyhow, Context, Result};
+use base64::prelude::*;
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::body::Incoming;
+use hyper::header::{HeaderMap, HOST};
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::{rustls, TlsConnector};
+
+use crate::content_encoding;
+use crate::har::{self, Har};
+
+/// Headers that only concern the hop between client, proxy and server.
+const HOP_BY_HOP_HEADERS: &[&str] = &[
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "proxy-connection",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+];
+
+/// Entries captured by the proxy, in order of completion.
+pub struct Recorder {
+    entries: Mutex<Vec<har::Entry>>,
+    /// Receives a copy of every entry, for a viewer showing the live capture.
+    live: Option<UnboundedSender<har::Entry>>,
+    /// Print one line per request to stdout.
+    pub verbose: bool,
+}
+
+impl Recorder {
+    pub fn new(live: Option<UnboundedSender<har::Entry>>) -> Self {
+        Self {
+            entries: Mutex::new(Vec::new()),
+            live,
+            verbose: false,
+        }
+    }
+
+    /// Everything recorded so far.
+    pub fn har(&self) -> Har {
+        Har::new(self.entries.lock().unwrap().clone())
+    }
+
+    fn record(&self, entry: har::Entry) {
+        if self.verbose {
+            println!(
+                "{} {} {} {:.0} ms",
+                entry.response.status, entry.request.method, entry.request.url, entry.time
+            );
+        }
+        if let Some(live) = &self.live {
+            let _ = live.send(entry.clone());
+        }
+        self.entries.lock().unwrap().push(entry);
+    }
+}
+
+/// Run the forward proxy on `listener` until the task is dropped.
+pub async fn run(recorder: Arc<Recorder>, listener: TcpListener) -> Result<()> {
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let recorder = recorder.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |req| {
+                let recorder = recorder.clone();
+                async move { Ok::<_, Infallible>(handle(&recorder, req).await) }
+            });
+            if let Err(e) = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .with_upgrades()
+                .await
+            {
+                if is_reportable(&e) {
+                    eprintln!("connection error: {}", e);
+                }
+            }
+        });
+    }
+}
+
+/// Clients routinely drop idle keep-alive connections; only report real failures.
+fn is_reportable(e: &hyper::Error) -> bool {
+    !(e.is_incomplete_message() || e.is_closed() || e.is_canceled())
+}
+
+async fn handle(recorder: &Recorder, req: Request<Incoming>) -> Response<Full<Bytes>> {
+    if req.method() == Method::CONNECT {
+        return tunnel(req);
+    }
+    let url = match url::Url::parse(&req.uri().to_string()) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => url,
+        Ok(url) => {
+            return error_response(
+                StatusCode::BAD_GATEWAY,
+                &format!("harview record cannot forward {} URLs", url.scheme()),
+            )
+        }
+        Err(_) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                "harview record is a forward proxy: send absolute URLs (set HTTP_PROXY)",
+            )
+        }
+    };
+
+    let started = chrono::Utc::now();
+    let (parts, body) = req.into_parts();
+    let request_body = body.collect().await.map(|b| b.to_bytes()).unwrap_or_default();
+    let request = har_request(&parts.method, &url, &parts.headers, &request_body);
+
+    let (response, entry) = match forward(&parts.method, &url, &parts.headers, request_body).await {
+        Ok(exchange) => {
+            let response = client_response(&exchange);
+            let entry = exchange.into_entry(started, request);
+            (response, entry)
+        }
+        Err(e) => {
+            let message = format!("{:#}", e);
+            let entry = failed_entry(started, request, &message);
+            (error_response(StatusCode::BAD_GATEWAY, &message), entry)
+        }
+    };
+    recorder.record(entry);
+    response
+}
+
+/// HTTPS goes through `CONNECT`; the tunnel is passed through unrecorded since
+/// the traffic inside is encrypted.
+fn tunnel(req: Request<Incoming>) -> Response<Full<Bytes>> {
+    let Some(authority) = req.uri().authority().map(|a| a.to_string()) else {
+        return error_response(StatusCode::BAD_REQUEST, "CONNECT needs host:port");
+    };
+    tokio::spawn(async move {
+        let Ok(upgraded) = hyper::upgrade::on(req).await else {
+            return;
+        };
+        if let Ok(mut server) = TcpStream::connect(&authority).await {
+            let _ = tokio::io::copy_bidirectional(&mut TokioIo::new(upgraded), &mut server).await;
+        }
+    });
+    Response::new(Full::new(Bytes::new()))
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(format!("{}\n", message))));
+    *response.status_mut() = status;
+    response
+}
+
+/// A request forwarded upstream, with what came back and how long each phase took.
+pub struct Exchange {
+    server_addr: SocketAddr,
+    local_port: u16,
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+    dns_ms: f64,
+    /// TCP connect plus TLS handshake, as HAR counts it.
+    connect_ms: f64,
+    ssl_ms: Option<f64>,
+    wait_ms: f64,
+    receive_ms: f64,
+}
+
+/// Send a request over a fresh HTTP/1.1 connection (TLS for `https`),
+/// timing each phase. `Host` is always set from `url`.
+pub async fn forward(method: &Method, url: &url::Url, headers: &HeaderMap, body: Bytes) -> Result<Exchange> {
+    let host = url.host_str().ok_or_else(|| anyhow!("URL has no host"))?;
+    let port = url.port_or_known_default().unwrap_or(80);
+
+    let t = Instant::now();
+    let server_addr = tokio::net::lookup_host((host, port))
+        .await
+        .with_context(|| format!("failed to resolve {}", host))?
+        .next()
+        .ok_or_else(|| anyhow!("no address for {}", host))?;
+    let dns_ms = elapsed_ms(t);
+
+    let t = Instant::now();
+    let stream = TcpStream::connect(server_addr)
+        .await
+        .with_context(|| format!("failed to connect to {}", server_addr))?;
+    let local_port = stream.local_addr()?.port();
+    let mut ssl_ms = None;
+    let mut sender = if url.scheme() == "https" {
+        let tls_start = Instant::now();
+        let server_name = ServerName::try_from(host.to_string())?;
+        let tls = tls_connector()
+            .connect(server_name, stream)
+            .await
+            .with_context(|| format!("TLS handshake with {} failed", host))?;
+        ssl_ms = Some(elapsed_ms(tls_start));
+        let (sender, conn) = hyper::client::conn::http1::handshake(TokioIo::new(tls)).await?;
+        tokio::spawn(conn);
+        sender
+    } else {
+        let (sender, conn) = hyper::client::conn::http1::handshake(TokioIo::new(stream)).await?;
+        tokio::spawn(conn);
+        sender
+    };
+    let connect_ms = elapsed_ms(t);
+
+    let target = match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    };
+    let authority = match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    };
+    let mut outgoing = Request::builder()
+        .method(method.clone())
+        .uri(target)
+        .header(HOST, authority);
+    for (name, value) in headers {
+        if !is_hop_by_hop(name.as_str()) && name != HOST {
+            outgoing = outgoing.header(name, value);
+        }
+    }
+    let outgoing = outgoing.body(Full::new(body))?;
+
+    let t = Instant::now();
+    let response = sender.send_request(outgoing).await?;
+    let wait_ms = elapsed_ms(t);
+
+    let t = Instant::now();
+    let (parts, body) = response.into_parts();
+    let body = body.collect().await?.to_bytes();
+    let receive_ms = elapsed_ms(t);
+
+    Ok(Exchange {
+        server_addr,
+        local_port,
+        status: parts.status,
+        headers: parts.headers,
+        body,
+        dns_ms,
+        connect_ms,
+        ssl_ms,
+        wait_ms,
+        receive_ms,
+    })
+}
+
+/// Client TLS configuration trusting the Mozilla root store.
+fn tls_connector() -> TlsConnector {
+    let roots = rustls::RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let config = rustls::ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    TlsConnector::from(Arc::new(config))
+}
+
+fn elapsed_ms(t: Instant) -> f64 {
+    t.elapsed().as_secs_f64() * 1000.0
+}
+
+fn is_hop_by_hop(name: &str) -> bool {
+    HOP_BY_HOP_HEADERS.iter().any(|h| h.eq_ignore_ascii_case(name))
+}
+
+/// The upstream response, passed to the client unchanged apart from hop-by-hop headers.
+fn client_response(exchange: &Exchange) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(exchange.body.clone()));
+    *response.status_mut() = exchange.status;
+    for (name, value) in &exchange.headers {
+        if !is_hop_by_hop(name.as_str()) {
+            response.headers_mut().append(name, value.clone());
+        }
+    }
+    response
+}
+
+impl Exchange {
+    pub fn into_entry(self, started: chrono::DateTime<chrono::Utc>, request: har::Request) -> har::Entry {
+        let headers = har_headers(&self.headers);
+        let decoded = content_encoding::decode(
+            self.headers.get("content-encoding").and_then(|v| v.to_str().ok()),
+            self.body.to_vec(),
+        );
+        let mime_type = self
+            .headers
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let (text, encoding) = body_text(&decoded.bytes);
+        let cookies = self
+            .headers
+            .get_all("set-cookie")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .filter_map(|v| v.split(';').next().and_then(parse_cookie))
+            .collect();
+        let timings = har::Timings {
+            blocked: None,
+            dns: Some(self.dns_ms),
+            ssl: self.ssl_ms,
+            connect: Some(self.connect_ms),
+            send: Some(0.0),
+            wait: Some(self.wait_ms),
+            receive: Some(self.receive_ms),
+            extra: Default::default(),
+        };
+        har::Entry {
+            started_date_time: started.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            time: self.dns_ms + self.connect_ms + self.wait_ms + self.receive_ms,
+            request,
+            response: har::Response {
+                status: self.status.as_u16() as i64,
+                status_text: self.status.canonical_reason().unwrap_or_default().to_string(),
+                http_version: "HTTP/1.1".to_string(),
+                redirect_url: self
+                    .headers
+                    .get("location")
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or_default()
+                    .to_string(),
+                headers,
+                cookies,
+                content: har::Content {
+                    mime_type,
+                    size: Some(decoded.bytes.len() as i64),
+                    text,
+                    encoding,
+                    extra: Default::default(),
+                },
+                headers_size: Some(-1),
+                body_size: Some(self.body.len() as i64),
+                extra: Default::default(),
+            },
+            cache: har::Cache::default(),
+            timings,
+            security_state: self.ssl_ms.map(|_| "secure".to_string()),
+            from_cache: None,
+            pageref: None,
+            server_ipaddress: Some(self.server_addr.ip().to_string()),
+            connection: Some(self.local_port.to_string()),
+            extra: Default::default(),
+        }
+    }
+}
+
+/// Entry for a request that never got a response; like browsers, status 0.
+pub fn failed_entry(started: chrono::DateTime<chrono::Utc>, request: har::Request, message: &str) -> har::Entry {
+    har::Entry {
+        started_date_time: started.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        time: (chrono::Utc::now() - started).num_microseconds().unwrap_or(0) as f64 / 1000.0,
+        request,
+        response: har::Response {
+            status: 0,
+            status_text: message.to_string(),
+            http_version: String::new(),
+            headers: vec![],
+            cookies: vec![],
+            content: har::Content {
+                mime_type: None,
+                size: Some(0),
+                text: None,
+                encoding: None,
+                extra: Default::default(),
+            },
+            redirect_url: String::new(),
+            headers_size: Some(-1),
+            body_size: Some(-1),
+            extra: Default::default(),
+        },
+        cache: har::Cache::default(),
+        timings: har::Timings {
+            blocked: None,
+            dns: None,
+            ssl: None,
+            connect: None,
+            send: Some(0.0),
+            wait: Some(0.0),
+            receive: Some(0.0),
+            extra: Default::default(),
+        },
+        security_state: None,
+        from_cache: None,
+        pageref: None,
+        server_ipaddress: None,
+        connection: None,
+        extra: Default::default(),
+    }
+}
+
+pub fn har_request(method: &Method, url: &url::Url, headers: &HeaderMap, body: &Bytes) -> har::Request {
+    let cookies = headers
+        .get_all("cookie")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .filter_map(parse_cookie)
+        .collect();
+    let post_data = (!body.is_empty()).then(|| {
+        let (text, encoding) = body_text(body);
+        let mut extra = har::Extra::default();
+        if encoding.is_some() {
+            let (key, value) = har::BASE64_MARKER;
+            extra.insert(key.to_string(), value.into());
+        }
+        har::PostData {
+            mime_type: headers
+                .get("content-type")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default()
+                .to_string(),
+            params: None,
+            text: text.unwrap_or_default(),
+            extra,
+        }
+    });
+    har::Request {
+        method: method.to_string(),
+        url: url.clone(),
+        http_version: "HTTP/1.1".to_string(),
+        headers: har_headers(headers)
+            .into_iter()
+            .filter(|h| !h.name.to_lowercase().starts_with("proxy-"))
+            .collect(),
+        cookies,
+        query_string: url
+            .query_pairs()
+            .map(|(name, value)| har::QueryString {
+                name: name.into_owned(),
+                value: value.into_owned(),
+                extra: Default::default(),
+            })
+            .collect(),
+        headers_size: Some(-1),
+        body_size: Some(body.len() as i64),
+        post_data,
+        extra: Default::default(),
+    }
+}
+
+pub fn har_headers(headers: &HeaderMap) -> Vec<har::Header> {
+    headers
+        .iter()
+        .map(|(name, value)| har::Header {
+            name: name.as_str().to_string(),
+            value: String::from_utf8_lossy(value.as_bytes()).into_owned(),
+            extra: Default::default(),
+        })
+        .collect()
+}
+
+/// `name=value` from a `Cookie` pair or the start of a `Set-Cookie` value.
+fn parse_cookie(pair: &str) -> Option<har::Cookie> {
+    let (name, value) = pair.trim().split_once('=')?;
+    Some(har::Cookie {
+        name: name.trim().to_string(),
+        value: value.trim().to_string(),
+        extra: Default::default(),
+    })
+}
+
+/// Body as HAR content text: UTF-8 as is, anything else base64-encoded.
+fn body_text(bytes: &[u8]) -> (Option<String>, Option<String>) {
+    if bytes.is_empty() {
+        return (None, None);
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(text) => (Some(text.to_string()), None),
+        Err(_) => (Some(BASE64_STANDARD.encode(bytes)), Some("base64".to_string())),
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Minimal upstream answering every request with a fixed response.
+    async fn upstream(response: &'static [u8]) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf).await;
+                let _ = stream.write_all(response).await;
+            }
+        });
+        addr
+    }
+
+    async fn proxy() -> (Arc<Recorder>, SocketAddr, tokio::sync::mpsc::UnboundedReceiver<har::Entry>) {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let recorder = Arc::new(Recorder::new(Some(tx)));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(run(recorder.clone(), listener));
+        (recorder, addr, rx)
+    }
+
+    async fn send(proxy: SocketAddr, request: String) -> String {
+        let mut stream = TcpStream::connect(proxy).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut raw = String::new();
+        stream.read_to_string(&mut raw).await.unwrap();
+        raw
+    }
+
+    #[tokio::test]
+    async fn records_forwarded_exchange() {
+        let server = upstream(
+            b"HTTP/1.1 201 Created\r\nContent-Type: application/json\r\nSet-Cookie: sid=abc; Path=/\r\nContent-Length: 11\r\nConnection: close\r\n\r\n{\"id\":true}",
+        )
+        .await;
+        let (recorder, addr, mut live) = proxy().await;
+
+        let raw = send(
+            addr,
+            format!(
+                "POST http://{}/items?page=2 HTTP/1.1\r\nHost: {}\r\nCookie: a=1; b=2\r\nProxy-Connection: keep-alive\r\nContent-Type: application/json\r\nContent-Length: 9\r\nConnection: close\r\n\r\n{{\"n\":\"x\"}}",
+                server, server
+            ),
+        )
+        .await;
+        assert!(raw.starts_with("HTTP/1.1 201 Created\r\n"), "{}", raw);
+        assert!(raw.ends_with("{\"id\":true}"));
+
+        let entry = live.recv().await.unwrap();
+        assert_eq!(entry.request.method, "POST");
+        assert_eq!(entry.request.url.as_str(), format!("http://{}/items?page=2", server));
+        assert_eq!(entry.request.query_string[0].value, "2");
+        assert_eq!(entry.request.cookies.len(), 2);
+        assert!(entry.request.header("proxy-connection").is_none());
+        assert_eq!(entry.request.post_data.as_ref().unwrap().text, r#"{"n":"x"}"#);
+        assert_eq!(entry.response.status, 201);
+        assert_eq!(entry.response.status_text, "Created");
+        assert_eq!(entry.response.content.text.as_deref(), Some(r#"{"id":true}"#));
+        assert_eq!(entry.response.cookies[0].value, "abc");
+        assert_eq!(entry.server_ipaddress.as_deref(), Some("127.0.0.1"));
+        assert!(entry.timings.wait.unwrap() >= 0.0);
+        assert_eq!(recorder.har().log.entries, vec![entry]);
+    }
+
+    #[tokio::test]
+    async fn unreachable_upstream_is_recorded_as_failed() {
+        // Bind and drop to get a port nothing listens on.
+        let closed = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap();
+        let (recorder, addr, _live) = proxy().await;
+        let raw = send(
+            addr,
+            format!("GET http://{}/ HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n", closed, closed),
+        )
+        .await;
+        assert!(raw.starts_with("HTTP/1.1 502"), "{}", raw);
+        let har = recorder.har();
+        assert_eq!(har.log.entries[0].response.status, 0);
+        assert!(har.log.entries[0].response.status_text.contains("failed to connect"));
+    }
+
+    #[tokio::test]
+    async fn origin_form_requests_are_rejected() {
+        let (recorder, addr, _live) = proxy().await;
+        let raw = send(addr, "GET /x HTTP/1.1\r\nHost: a\r\nConnection: close\r\n\r\n".to_string()).await;
+        assert!(raw.starts_with("HTTP/1.1 400"), "{}", raw);
+        assert!(recorder.har().log.entries.is_empty());
+    }
+
+    #[test]
+    fn tls_connector_uses_bundled_roots() {
+        // Panics if no crypto provider is available.
+        let _ = tls_connector();
+    }
+
+    #[test]
+    fn binary_bodies_are_base64_encoded() {
+        assert_eq!(body_text(b"hi"), (Some("hi".to_string()), None));
+        assert_eq!(
+            body_text(&[0xff, 0x00]),
+            (Some("/wA=".to_string()), Some("base64".to_string()))
+        );
+        assert_eq!(body_text(b""), (None, None));
+    }
+
+    #[test]
+    fn binary_request_bodies_keep_their_bytes() {
+        let url = url::Url::parse("http://x.test/upload").unwrap();
+        let body = Bytes::from_static(&[0x89, b'P', b'N', b'G', 0xff, 0x00]);
+        let request = har_request(&Method::POST, &url, &HeaderMap::new(), &body);
+        let post_data = request.post_data.unwrap();
+        assert_eq!(post_data.text, "iVBOR/8A");
+        assert!(post_data.is_base64());
+        assert_eq!(post_data.bytes(), body.to_vec());
+
+        let request = har_request(&Method::POST, &url, &HeaderMap::new(), &Bytes::from_static(b"a=1"));
+        let post_data = request.post_data.unwrap();
+        assert!(!post_data.is_base64());
+        assert_eq!(post_data.bytes(), b"a=1");
+    }
+}
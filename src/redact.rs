@@ -0,0 +1,632 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use hmac::{Mac, SimpleHmac};
+use sha2::Sha256;
+
+use crate::app;
+use crate::har::{self, Har};
+
+/// Headers masked by default: credentials, session cookies and CSRF tokens.
+pub const DEFAULT_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+    "x-auth-token",
+    "x-access-token",
+    "x-csrf-token",
+    "x-xsrf-token",
+    "x-amz-security-token",
+];
+
+/// Cookies masked by default; `*` matches every name.
+pub const DEFAULT_COOKIES: &[&str] = &["*"];
+
+/// Query parameters (and form fields) masked by default.
+pub const DEFAULT_QUERY: &[&str] = &[
+    "access_token",
+    "refresh_token",
+    "id_token",
+    "token",
+    "api_key",
+    "apikey",
+    "key",
+    "secret",
+    "client_secret",
+    "password",
+    "code",
+    "signature",
+    "sig",
+    "x-amz-signature",
+    "x-amz-credential",
+    "x-amz-security-token",
+];
+
+/// JSON object keys whose values are masked by default, at any depth.
+pub const DEFAULT_JSON_KEYS: &[&str] = &[
+    "password",
+    "passwd",
+    "secret",
+    "client_secret",
+    "token",
+    "access_token",
+    "refresh_token",
+    "id_token",
+    "api_key",
+    "apikey",
+    "authorization",
+    "ssn",
+    "card_number",
+    "cvv",
+    "email",
+    "phone",
+];
+
+/// What to mask. Names are compared case-insensitively; `*` matches any name.
+#[derive(Debug, Clone)]
+pub struct RedactOptions {
+    pub headers: Vec<String>,
+    pub cookies: Vec<String>,
+    pub query: Vec<String>,
+    pub json_keys: Vec<String>,
+    /// Replace values with a short HMAC-SHA256 of themselves under this key
+    /// instead of `REDACTED`, so equal values stay recognisable across entries
+    /// without being recoverable by anyone who lacks the key.
+    pub hash_key: Option<Vec<u8>>,
+}
+
+impl Default for RedactOptions {
+    fn default() -> Self {
+        let owned = |names: &[&str]| names.iter().map(|n| n.to_string()).collect();
+        Self {
+            headers: owned(DEFAULT_HEADERS),
+            cookies: owned(DEFAULT_COOKIES),
+            query: owned(DEFAULT_QUERY),
+            json_keys: owned(DEFAULT_JSON_KEYS),
+            hash_key: None,
+        }
+    }
+}
+
+/// A fresh key for `hash_key`, so hashes only correlate within one run.
+pub fn random_hash_key() -> anyhow::Result<Vec<u8>> {
+    let mut key = vec![0; 32];
+    getrandom::getrandom(&mut key).map_err(|err| anyhow::anyhow!("failed to generate a hash key: {}", err))?;
+    Ok(key)
+}
+
+fn listed(names: &[String], name: &str) -> bool {
+    names.iter().any(|n| n == "*" || n.eq_ignore_ascii_case(name))
+}
+
+/// Where a masked value was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Location {
+    Header,
+    Cookie,
+    Query,
+    Form,
+    Json,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Location::Header => "header",
+            Location::Cookie => "cookie",
+            Location::Query => "query",
+            Location::Form => "form",
+            Location::Json => "json",
+        })
+    }
+}
+
+/// What was masked: counts per location and (lowercased) name.
+#[derive(Debug, Default)]
+pub struct Report {
+    pub counts: BTreeMap<(Location, String), usize>,
+    /// Entries with at least one masked value.
+    pub entries: usize,
+}
+
+impl Report {
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// One line, e.g. `42 values redacted in 17 entries`.
+    pub fn summary(&self) -> String {
+        format!("{} values redacted in {} entries", self.total(), self.entries)
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = format!("{}\n", self.summary());
+        let width = self.counts.keys().map(|(_, name)| name.len()).max().unwrap_or(0);
+        for ((location, name), count) in &self.counts {
+            out.push_str(&format!("  {:<7} {:<width$}  ×{}\n", location, name, count, width = width));
+        }
+        out
+    }
+}
+
+/// A copy of `har` with the values selected by `options` masked, and a report
+/// of what was masked. Bodies are only rewritten when something in them was.
+pub fn redact(har: &Har, options: &RedactOptions) -> (Har, Report) {
+    let mut redactor = Redactor {
+        options,
+        report: Report::default(),
+        touched: false,
+    };
+    let mut har = har.clone();
+    for entry in &mut har.log.entries {
+        redactor.touched = false;
+        redactor.entry(entry);
+        if redactor.touched {
+            redactor.report.entries += 1;
+        }
+    }
+    (har, redactor.report)
+}
+
+/// Where the viewer writes a sanitized export: `<name>.redacted.har` next to
+/// the opened file, or in the working directory.
+pub fn export_path(source: Option<&Path>) -> PathBuf {
+    match source {
+        Some(path) => {
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            path.with_file_name(format!("{}.redacted.har", stem))
+        }
+        None => PathBuf::from("harview.redacted.har"),
+    }
+}
+
+struct Redactor<'a> {
+    options: &'a RedactOptions,
+    report: Report,
+    /// Whether the current entry had anything masked.
+    touched: bool,
+}
+
+impl Redactor<'_> {
+    /// Record a masked value and return its replacement.
+    fn mask(&mut self, location: Location, name: &str, value: &str) -> String {
+        *self
+            .report
+            .counts
+            .entry((location, name.to_lowercase()))
+            .or_default() += 1;
+        self.touched = true;
+        self.replacement(value)
+    }
+
+    /// Replacement for values that are reported elsewhere, such as the
+    /// `queryString` copy of URL parameters.
+    fn replacement(&self, value: &str) -> String {
+        let Some(key) = &self.options.hash_key else {
+            return "REDACTED".to_string();
+        };
+        let mut mac = SimpleHmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+        mac.update(value.as_bytes());
+        let digest = mac.finalize().into_bytes();
+        let hex: String = digest.iter().take(8).map(|b| format!("{:02x}", b)).collect();
+        format!("redacted-{}", hex)
+    }
+
+    fn entry(&mut self, entry: &mut har::Entry) {
+        let request = &mut entry.request;
+        self.headers(&mut request.headers);
+        self.cookies(&mut request.cookies);
+        self.url(&mut request.url);
+        for param in &mut request.query_string {
+            if listed(&self.options.query, &param.name) {
+                param.value = self.replacement(&param.value);
+            }
+        }
+        if let Some(post_data) = &mut request.post_data {
+            self.post_data(post_data);
+        }
+
+        let response = &mut entry.response;
+        self.headers(&mut response.headers);
+        self.cookies(&mut response.cookies);
+        if let Ok(mut url) = url::Url::parse(&response.redirect_url) {
+            if self.url(&mut url) {
+                response.redirect_url = url.to_string();
+            }
+        }
+        self.response_body(entry);
+    }
+
+    fn headers(&mut self, headers: &mut [har::Header]) {
+        for header in headers {
+            if listed(&self.options.headers, &header.name) {
+                header.value = self.mask(Location::Header, &header.name, &header.value);
+            } else if header.name.eq_ignore_ascii_case("cookie") {
+                header.value = self.cookie_pairs(&header.value);
+            } else if header.name.eq_ignore_ascii_case("set-cookie") {
+                // Only the leading `name=value` is the cookie; the rest are attributes.
+                let (pair, attributes) = match header.value.split_once(';') {
+                    Some((pair, attributes)) => (pair, Some(attributes)),
+                    None => (header.value.as_str(), None),
+                };
+                let pair = self.cookie_pairs(pair);
+                header.value = match attributes {
+                    Some(attributes) => format!("{};{}", pair, attributes),
+                    None => pair,
+                };
+            } else if header.name.eq_ignore_ascii_case("location") {
+                if let Ok(mut url) = url::Url::parse(&header.value) {
+                    if self.url(&mut url) {
+                        header.value = url.to_string();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Mask listed cookies in a `a=1; b=2` string.
+    fn cookie_pairs(&mut self, value: &str) -> String {
+        value
+            .split(';')
+            .map(|pair| {
+                let pair = pair.trim();
+                match pair.split_once('=') {
+                    Some((name, value)) if listed(&self.options.cookies, name) => {
+                        format!("{}={}", name, self.mask(Location::Cookie, name, value))
+                    }
+                    _ => pair.to_string(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    fn cookies(&mut self, cookies: &mut [har::Cookie]) {
+        for cookie in cookies {
+            if listed(&self.options.cookies, &cookie.name) {
+                cookie.value = self.mask(Location::Cookie, &cookie.name, &cookie.value);
+            }
+        }
+    }
+
+    /// Mask listed query parameters of `url`; returns whether any were.
+    fn url(&mut self, url: &mut url::Url) -> bool {
+        let pairs: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+        if !pairs.iter().any(|(name, _)| listed(&self.options.query, name)) {
+            return false;
+        }
+        let pairs: Vec<(String, String)> = pairs
+            .into_iter()
+            .map(|(name, value)| {
+                if listed(&self.options.query, &name) {
+                    let masked = self.mask(Location::Query, &name, &value);
+                    (name, masked)
+                } else {
+                    (name, value)
+                }
+            })
+            .collect();
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+        true
+    }
+
+    fn form_field(&self, name: &str) -> bool {
+        listed(&self.options.query, name) || listed(&self.options.json_keys, name)
+    }
+
+    fn post_data(&mut self, post_data: &mut har::PostData) {
+        let mime = post_data.mime_type.to_lowercase();
+        if let Some(params) = &mut post_data.params {
+            for param in params {
+                if self.form_field(&param.name) {
+                    param.value = self.replacement(&param.value);
+                }
+            }
+        }
+        if mime.contains("json") {
+            if let Some(text) = self.json_text(&post_data.text) {
+                post_data.text = text;
+            }
+        } else if mime.contains("x-www-form-urlencoded") {
+            let pairs: Vec<(String, String)> = url::form_urlencoded::parse(post_data.text.as_bytes())
+                .into_owned()
+                .collect();
+            if pairs.iter().any(|(name, _)| self.form_field(name)) {
+                let pairs: Vec<(String, String)> = pairs
+                    .into_iter()
+                    .map(|(name, value)| {
+                        if self.form_field(&name) {
+                            let masked = self.mask(Location::Form, &name, &value);
+                            (name, masked)
+                        } else {
+                            (name, value)
+                        }
+                    })
+                    .collect();
+                post_data.text = url::form_urlencoded::Serializer::new(String::new())
+                    .extend_pairs(pairs)
+                    .finish();
+            }
+        }
+    }
+
+    /// JSON response bodies are decoded (base64, compression, charset) before
+    /// masking and stored back as plain text.
+    fn response_body(&mut self, entry: &mut har::Entry) {
+        let is_json = entry
+            .response
+            .content
+            .mime_type
+            .as_deref()
+            .is_some_and(|m| m.to_lowercase().contains("json"));
+        if !is_json {
+            return;
+        }
+        let Some(text) = app::response_text(entry) else {
+            return;
+        };
+        if let Some(redacted) = self.json_text(&text) {
+            let content = &mut entry.response.content;
+            content.size = Some(redacted.len() as i64);
+            content.text = Some(redacted);
+            content.encoding = None;
+        }
+    }
+
+    /// `text` with the values of listed keys masked, if it is JSON and anything
+    /// was masked. Everything else, including key order and whitespace, is
+    /// copied through unchanged.
+    fn json_text(&mut self, text: &str) -> Option<String> {
+        serde_json::from_str::<serde::de::IgnoredAny>(text).ok()?;
+        let start = skip_whitespace(text, 0);
+        let mut out = text[..start].to_string();
+        let end = self.json(text, start, &mut out);
+        out.push_str(&text[end..]);
+        (out != text).then_some(out)
+    }
+
+    /// Copy the JSON value starting at `start` into `out`, masking the values
+    /// of listed keys, and return where it ends. `text` is known to be valid.
+    fn json(&mut self, text: &str, start: usize, out: &mut String) -> usize {
+        let bytes = text.as_bytes();
+        let close = match bytes[start] {
+            b'{' => b'}',
+            b'[' => b']',
+            _ => {
+                let end = value_end(text, start);
+                out.push_str(&text[start..end]);
+                return end;
+            }
+        };
+        out.push(bytes[start] as char);
+        let mut pos = start + 1;
+        loop {
+            let next = skip_whitespace(text, pos);
+            out.push_str(&text[pos..next]);
+            pos = next;
+            match bytes[pos] {
+                b',' => {
+                    out.push(',');
+                    pos += 1;
+                    continue;
+                }
+                b if b == close => {
+                    out.push(b as char);
+                    return pos + 1;
+                }
+                _ => {}
+            }
+            if close == b']' {
+                pos = self.json(text, pos, out);
+                continue;
+            }
+            let key_end = value_end(text, pos);
+            let key: String = serde_json::from_str(&text[pos..key_end]).unwrap_or_default();
+            let value_start = skip_whitespace(text, skip_whitespace(text, key_end) + 1);
+            out.push_str(&text[pos..value_start]);
+            let raw = &text[value_start..value_end(text, value_start)];
+            if listed(&self.options.json_keys, &key) && raw != "null" {
+                let original = match serde_json::from_str(raw) {
+                    Ok(serde_json::Value::String(s)) => s,
+                    Ok(other) => other.to_string(),
+                    Err(_) => raw.to_string(),
+                };
+                let masked = self.mask(Location::Json, &key, &original);
+                out.push_str(&serde_json::Value::String(masked).to_string());
+                pos = value_start + raw.len();
+            } else {
+                pos = self.json(text, value_start, out);
+            }
+        }
+    }
+}
+
+fn skip_whitespace(text: &str, pos: usize) -> usize {
+    text.as_bytes()[pos..]
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .map_or(text.len(), |n| pos + n)
+}
+
+/// End of the JSON value starting at `start`: past the closing quote of a
+/// string, the matching bracket of an object or array, or the last character
+/// of a number or literal.
+fn value_end(text: &str, start: usize) -> usize {
+    let bytes = text.as_bytes();
+    let mut pos = start;
+    let mut depth = 0;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'"' => {
+                pos += 1;
+                while pos < bytes.len() && bytes[pos] != b'"' {
+                    pos += if bytes[pos] == b'\\' { 2 } else { 1 };
+                }
+            }
+            b'}' | b']' | b',' | b':' if depth == 0 => return pos,
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => depth -= 1,
+            b if b.is_ascii_whitespace() && depth == 0 => return pos,
+            _ => {}
+        }
+        pos += 1;
+        if depth == 0 && matches!(bytes[pos - 1], b'"' | b'}' | b']') {
+            return pos;
+        }
+    }
+    pos
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Har {
+        let mut entry = har::Entry::test("POST", "https://api.example.com/login?access_token=abc&page=2", 200)
+            .with_request_headers(&[("Authorization", "Bearer abc"), ("Accept", "application/json")])
+            .with_request_cookies(&[("session", "s3cr3t")])
+            .with_request_body(
+                "application/json",
+                r#"{"user":{"email":"bob@example.com","password":"hunter2"},"remember":true}"#,
+            )
+            .with_response_headers(&[("Set-Cookie", "session=s3cr3t; Path=/; HttpOnly")])
+            .with_response_body("application/json", r#"{"items":[{"id":1,"token":"t1"},{"id":2,"token":null}]}"#);
+        entry.request.query_string = [("access_token", "abc"), ("page", "2")]
+            .into_iter()
+            .map(|(name, value)| har::QueryString {
+                name: name.to_string(),
+                value: value.to_string(),
+                extra: Default::default(),
+            })
+            .collect();
+        Har::new(vec![entry.clone(), entry])
+    }
+
+    #[test]
+    fn defaults_mask_credentials_and_pii() {
+        let (har, report) = redact(&sample(), &RedactOptions::default());
+        let entry = &har.log.entries[0];
+        let request = &entry.request;
+        assert_eq!(request.header("authorization"), Some("REDACTED"));
+        assert_eq!(request.header("accept"), Some("application/json"));
+        assert_eq!(request.cookies[0].value, "REDACTED");
+        assert_eq!(request.url.query(), Some("access_token=REDACTED&page=2"));
+        assert_eq!(request.query_string[0].value, "REDACTED");
+        assert_eq!(request.query_string[1].value, "2");
+        assert_eq!(
+            request.post_data.as_ref().unwrap().text,
+            r#"{"user":{"email":"REDACTED","password":"REDACTED"},"remember":true}"#
+        );
+        assert_eq!(entry.response.header("set-cookie"), Some("REDACTED"));
+        // Null tokens are left alone.
+        assert_eq!(
+            entry.response.content.text.as_deref(),
+            Some(r#"{"items":[{"id":1,"token":"REDACTED"},{"id":2,"token":null}]}"#)
+        );
+
+        assert_eq!(report.entries, 2);
+        assert_eq!(report.counts[&(Location::Header, "authorization".to_string())], 2);
+        assert_eq!(report.counts[&(Location::Query, "access_token".to_string())], 2);
+        assert_eq!(report.counts[&(Location::Json, "token".to_string())], 2);
+        // authorization, session cookie, access_token, email, password, set-cookie, token
+        assert_eq!(report.total(), 14);
+        assert!(report.render().contains("header  authorization  ×2"));
+    }
+
+    #[test]
+    fn hashes_are_stable_and_correlate() {
+        let keyed = |key: &[u8]| RedactOptions {
+            hash_key: Some(key.to_vec()),
+            ..RedactOptions::default()
+        };
+        let (har, _) = redact(&sample(), &keyed(b"run-1"));
+        let first = &har.log.entries[0].request;
+        let second = &har.log.entries[1].request;
+        assert!(first.cookies[0].value.starts_with("redacted-"));
+        assert_eq!(first.cookies[0].value.len(), "redacted-".len() + 16);
+        assert_ne!(first.cookies[0].value, "redacted-s3cr3t");
+        assert_eq!(first.cookies[0].value, second.cookies[0].value);
+        assert_ne!(first.cookies[0].value, first.header("authorization").unwrap());
+
+        // The same key gives the same hashes; another key gives unrelated ones.
+        let (again, _) = redact(&sample(), &keyed(b"run-1"));
+        assert_eq!(again.log.entries[0].request.cookies[0].value, first.cookies[0].value);
+        let (other, _) = redact(&sample(), &keyed(b"run-2"));
+        assert_ne!(other.log.entries[0].request.cookies[0].value, first.cookies[0].value);
+        assert_ne!(random_hash_key().unwrap(), random_hash_key().unwrap());
+    }
+
+    #[test]
+    fn json_bodies_keep_their_formatting() {
+        let text = r#"{
+  "zeta": "a \"quoted\" }",
+  "token" :  {"inner": [1, 2]},
+  "list": [ {"password":"p"}, 1.5e3 ],
+  "email": null
+}
+"#;
+        let mut har = sample();
+        har.log.entries[0].request.post_data.as_mut().unwrap().text = text.to_string();
+        let (har, _) = redact(&har, &RedactOptions::default());
+        let expected = text
+            .replace(r#"{"inner": [1, 2]}"#, r#""REDACTED""#)
+            .replace(r#""p""#, r#""REDACTED""#);
+        assert_eq!(har.log.entries[0].request.post_data.as_ref().unwrap().text, expected);
+    }
+
+    #[test]
+    fn cookie_headers_keep_unlisted_cookies() {
+        let mut har = sample();
+        har.log.entries[0].request.headers = vec![har::Header {
+            name: "Cookie".to_string(),
+            value: "session=s3cr3t; theme=dark".to_string(),
+            extra: Default::default(),
+        }];
+        har.log.entries[0].response.headers[0].value = "session=s3cr3t; Path=/".to_string();
+        let options = RedactOptions {
+            headers: vec![],
+            cookies: vec!["session".to_string()],
+            query: vec![],
+            json_keys: vec![],
+            hash_key: None,
+        };
+        let (har, report) = redact(&har, &options);
+        let entry = &har.log.entries[0];
+        assert_eq!(entry.request.header("cookie"), Some("session=REDACTED; theme=dark"));
+        assert_eq!(entry.response.header("set-cookie"), Some("session=REDACTED; Path=/"));
+        assert_eq!(entry.request.url.query(), Some("access_token=abc&page=2"));
+        assert_eq!(report.entries, 2);
+    }
+
+    #[test]
+    fn form_bodies_and_export_path() {
+        let mut har = sample();
+        har.log.entries[0].request.post_data = Some(har::PostData {
+            mime_type: "application/x-www-form-urlencoded".to_string(),
+            params: None,
+            text: "username=bob&password=hunter+2".to_string(),
+            extra: Default::default(),
+        });
+        let (har, report) = redact(&har, &RedactOptions::default());
+        assert_eq!(
+            har.log.entries[0].request.post_data.as_ref().unwrap().text,
+            "username=bob&password=REDACTED"
+        );
+        assert_eq!(report.counts[&(Location::Form, "password".to_string())], 1);
+
+        assert_eq!(
+            export_path(Some(Path::new("/tmp/session.har"))),
+            PathBuf::from("/tmp/session.redacted.har")
+        );
+        assert_eq!(export_path(None), PathBuf::from("harview.redacted.har"));
+    }
+}
@@ -0,0 +1,174 @@
+use crate::har;
+
+/// Where a 3xx response sends the browser: `redirectURL`, or else the
+/// `Location` header, resolved against the request URL. `None` for other
+/// responses and for 304 Not Modified.
+pub fn target(entry: &har::Entry) -> Option<url::Url> {
+    let response = &entry.response;
+    if !(300..400).contains(&response.status) || response.status == 304 {
+        return None;
+    }
+    let location = Some(response.redirect_url.as_str())
+        .filter(|url| !url.is_empty())
+        .or_else(|| response.header("location"))?;
+    entry.request.url.join(location.trim()).ok()
+}
+
+fn same_resource(a: &url::Url, b: &url::Url) -> bool {
+    let mut a = a.clone();
+    let mut b = b.clone();
+    // The fragment never reaches the server.
+    a.set_fragment(None);
+    b.set_fragment(None);
+    a == b
+}
+
+/// The entry that followed the redirect of the entry at `index`: the first
+/// later request to its target.
+pub fn next_hop(entries: &[har::Entry], index: usize) -> Option<usize> {
+    let target = target(entries.get(index)?)?;
+    entries
+        .iter()
+        .enumerate()
+        .skip(index + 1)
+        .find(|(_, e)| same_resource(&e.request.url, &target))
+        .map(|(i, _)| i)
+}
+
+/// The redirect that led to the entry at `index`, if any.
+pub fn previous_hop(entries: &[har::Entry], index: usize) -> Option<usize> {
+    let url = &entries.get(index)?.request.url;
+    (0..index).rev().find(|&i| {
+        target(&entries[i]).is_some_and(|t| same_resource(&t, url)) && next_hop(entries, i) == Some(index)
+    })
+}
+
+/// A redirect chain, from the first request to the response that ended it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chain {
+    /// Indices into `har.log.entries`, in the order the browser followed them.
+    pub hops: Vec<usize>,
+    /// Position in `hops` of the first hop requesting a URL already visited.
+    pub loop_at: Option<usize>,
+    /// Positions in `hops` of redirects from HTTPS to plain HTTP.
+    pub downgrades: Vec<usize>,
+    /// Target of the last hop when the capture does not contain its request.
+    pub unfollowed: Option<url::Url>,
+}
+
+impl Chain {
+    /// Total time of all hops, in milliseconds.
+    pub fn time(&self, entries: &[har::Entry]) -> f64 {
+        self.hops.iter().map(|&i| entries[i].time).sum()
+    }
+}
+
+/// The redirect chain the entry at `index` is part of, or `None` when it
+/// neither redirects nor was reached by a redirect.
+pub fn chain(entries: &[har::Entry], index: usize) -> Option<Chain> {
+    let mut first = index;
+    while let Some(previous) = previous_hop(entries, first) {
+        first = previous;
+    }
+    let mut hops = vec![first];
+    while let Some(next) = next_hop(entries, *hops.last()?) {
+        hops.push(next);
+    }
+    let last = &entries[*hops.last()?];
+    let unfollowed = target(last);
+    if hops.len() == 1 && unfollowed.is_none() {
+        return None;
+    }
+
+    let urls: Vec<&url::Url> = hops.iter().map(|&i| &entries[i].request.url).collect();
+    let repeats = |position: usize, url: &url::Url| urls[..position].iter().any(|u| same_resource(u, url));
+    let loop_at = (1..urls.len())
+        .find(|&p| repeats(p, urls[p]))
+        .or_else(|| unfollowed.as_ref().filter(|t| repeats(urls.len(), t)).map(|_| urls.len()));
+
+    let downgrades = hops
+        .iter()
+        .enumerate()
+        .filter(|(_, &i)| {
+            entries[i].request.url.scheme() == "https" && target(&entries[i]).is_some_and(|t| t.scheme() == "http")
+        })
+        .map(|(position, _)| position)
+        .collect();
+
+    Some(Chain {
+        hops,
+        loop_at,
+        downgrades,
+        unfollowed,
+    })
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(url: &str, status: i64, location: &str) -> har::Entry {
+        let mut entry = har::Entry::test("GET", url, status).with_time(20.0);
+        if !location.is_empty() {
+            entry = entry.with_response_headers(&[("Location", location)]);
+        }
+        entry.response.content.mime_type = Some("text/html".to_string());
+        entry
+    }
+
+    #[test]
+    fn resolves_targets() {
+        let mut moved = entry("https://x.test/a/b", 302, "../c?q=1");
+        assert_eq!(target(&moved).unwrap().as_str(), "https://x.test/c?q=1");
+        moved.response.redirect_url = "https://y.test/".to_string();
+        assert_eq!(target(&moved).unwrap().as_str(), "https://y.test/");
+        assert!(target(&entry("https://x.test/", 304, "/elsewhere")).is_none());
+        assert!(target(&entry("https://x.test/", 200, "")).is_none());
+    }
+
+    #[test]
+    fn follows_a_chain_from_any_hop() {
+        let entries = vec![
+            entry("http://x.test/", 301, "https://x.test/"),
+            entry("https://x.test/style.css", 200, ""),
+            entry("https://x.test/", 302, "/login#top"),
+            entry("https://x.test/login", 200, ""),
+        ];
+        let expected = Chain {
+            hops: vec![0, 2, 3],
+            loop_at: None,
+            downgrades: vec![],
+            unfollowed: None,
+        };
+        for index in [0, 2, 3] {
+            assert_eq!(chain(&entries, index).as_ref(), Some(&expected), "from #{}", index);
+        }
+        assert_eq!(chain(&entries, 1), None);
+        assert_eq!(expected.time(&entries), 60.0);
+    }
+
+    #[test]
+    fn detects_loops_and_downgrades() {
+        let entries = vec![
+            entry("https://x.test/a", 302, "http://x.test/b"),
+            entry("http://x.test/b", 302, "https://x.test/a"),
+            entry("https://x.test/a", 302, "http://x.test/b"),
+        ];
+        let chain = chain(&entries, 1).unwrap();
+        assert_eq!(chain.hops, vec![0, 1, 2]);
+        assert_eq!(chain.loop_at, Some(2));
+        assert_eq!(chain.downgrades, vec![0, 2]);
+        assert_eq!(chain.unfollowed.unwrap().as_str(), "http://x.test/b");
+    }
+
+    #[test]
+    fn unfollowed_redirects_form_a_chain() {
+        let entries = vec![entry("https://x.test/", 307, "https://y.test/")];
+        let chain = chain(&entries, 0).unwrap();
+        assert_eq!(chain.hops, vec![0]);
+        assert_eq!(chain.unfollowed.unwrap().as_str(), "https://y.test/");
+        assert_eq!(chain.loop_at, None);
+    }
+}
@@ -0,0 +1,284 @@
+use anyhow::{anyhow, bail, Context, Result};
+use bytes::Bytes;
+use hyper::header::{HeaderMap, HeaderName, HeaderValue};
+use hyper::Method;
+
+use crate::har;
+use crate::recorder;
+
+/// Request headers not replayed: pseudo-headers of HTTP/2 recordings and
+/// headers that are recomputed for the new target and body.
+const DROPPED_HEADERS: &[&str] = &["content-length", "host"];
+
+/// `url` moved to the origin of `base`, e.g. `http://localhost:3000`. A path in
+/// `base` is prepended, so `http://localhost:3000/api` maps `/v1/users` to
+/// `/api/v1/users`.
+pub fn rebase(url: &url::Url, base: &str) -> Result<url::Url> {
+    let base = url::Url::parse(base.trim()).with_context(|| format!("invalid base URL {:?}", base))?;
+    if !matches!(base.scheme(), "http" | "https") {
+        bail!("base URL must be http or https");
+    }
+    let mut rebased = base.clone();
+    rebased.set_path(&format!("{}{}", base.path().trim_end_matches('/'), url.path()));
+    rebased.set_query(url.query());
+    Ok(rebased)
+}
+
+/// Scheme, host and port of a URL, the default replay target.
+pub fn origin(url: &url::Url) -> String {
+    url.origin().ascii_serialization()
+}
+
+/// The request as editable HTTP text: request line, headers, blank line, body.
+/// A body is followed by a newline, the one editors add to the last line;
+/// [`parse_http_text`] drops it again.
+pub fn to_http_text(request: &har::Request) -> String {
+    let mut text = format!("{} {}\n", request.method, request.url);
+    for header in &request.headers {
+        if !header.name.starts_with(':') {
+            text.push_str(&format!("{}: {}\n", header.name, header.value));
+        }
+    }
+    text.push('\n');
+    if let Some(post_data) = request.post_data.as_ref().filter(|p| !p.text.is_empty()) {
+        text.push_str(&post_data.text);
+        text.push('\n');
+    }
+    text
+}
+
+/// Parse text written by [`to_http_text`] (possibly edited) back into a request.
+pub fn parse_http_text(text: &str) -> Result<har::Request> {
+    let (head, body) = match text.split_once("\n\n") {
+        Some((head, body)) => (head, body.strip_suffix('\n').unwrap_or(body)),
+        None => (text.trim_end_matches('\n'), ""),
+    };
+    let mut lines = head.lines();
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(url)) = (parts.next(), parts.next()) else {
+        bail!("first line must be `METHOD URL`");
+    };
+    Method::from_bytes(method.as_bytes()).map_err(|_| anyhow!("invalid method {:?}", method))?;
+    let url = url::Url::parse(url).with_context(|| format!("invalid URL {:?}", url))?;
+
+    let mut headers = Vec::new();
+    for line in lines.filter(|l| !l.trim().is_empty()) {
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| anyhow!("header line without `:`: {:?}", line))?;
+        headers.push(har::Header {
+            name: name.trim().to_string(),
+            value: value.trim().to_string(),
+            extra: Default::default(),
+        });
+    }
+    let post_data = (!body.is_empty()).then(|| har::PostData {
+        mime_type: har_header(&headers, "content-type").unwrap_or_default().to_string(),
+        params: None,
+        text: body.to_string(),
+        extra: Default::default(),
+    });
+
+    Ok(har::Request {
+        body_size: Some(body.len() as i64),
+        method: method.to_string(),
+        query_string: url
+            .query_pairs()
+            .map(|(name, value)| har::QueryString {
+                name: name.into_owned(),
+                value: value.into_owned(),
+                extra: Default::default(),
+            })
+            .collect(),
+        url,
+        http_version: "HTTP/1.1".to_string(),
+        headers,
+        cookies: vec![],
+        headers_size: Some(-1),
+        post_data,
+        extra: Default::default(),
+    })
+}
+
+/// A binary body is edited as its base64 text: when that text comes back
+/// unchanged, `edited` sends the recorded bytes again rather than the base64.
+pub fn keep_binary_body(edited: &mut har::Request, original: &har::Request) {
+    let (Some(edited_body), Some(original_body)) = (edited.post_data.as_mut(), original.post_data.as_ref()) else {
+        return;
+    };
+    if original_body.is_base64() && edited_body.text == original_body.text {
+        edited_body.extra = original_body.extra.clone();
+        edited.body_size = original.body_size;
+    }
+}
+
+fn har_header<'a>(headers: &'a [har::Header], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|h| h.name.eq_ignore_ascii_case(name))
+        .map(|h| h.value.as_str())
+}
+
+/// Send `request` and record the exchange as an entry. Network failures give
+/// an entry with status 0 and the error as its status text.
+pub async fn send(request: har::Request) -> har::Entry {
+    let started = chrono::Utc::now();
+    let method = match Method::from_bytes(request.method.as_bytes()) {
+        Ok(method) => method,
+        Err(_) => return recorder::failed_entry(started, request, "invalid method"),
+    };
+    let mut headers = HeaderMap::new();
+    for header in &request.headers {
+        if header.name.starts_with(':')
+            || DROPPED_HEADERS.iter().any(|h| h.eq_ignore_ascii_case(&header.name))
+        {
+            continue;
+        }
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(header.name.as_bytes()),
+            HeaderValue::from_str(&header.value),
+        ) {
+            headers.append(name, value);
+        }
+    }
+    let body = request
+        .post_data
+        .as_ref()
+        .map(|p| Bytes::from(p.bytes()))
+        .unwrap_or_default();
+
+    match recorder::forward(&method, &request.url, &headers, body).await {
+        Ok(exchange) => exchange.into_entry(started, request),
+        Err(e) => recorder::failed_entry(started, request, &format!("{:#}", e)),
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn url(s: &str) -> url::Url {
+        url::Url::parse(s).unwrap()
+    }
+
+    #[test]
+    fn rebase_keeps_path_and_query() {
+        let recorded = url("https://api.example.com/v1/users?page=2#top");
+        assert_eq!(
+            rebase(&recorded, "http://localhost:3000").unwrap().as_str(),
+            "http://localhost:3000/v1/users?page=2"
+        );
+        assert_eq!(
+            rebase(&recorded, "http://localhost:3000/api/").unwrap().as_str(),
+            "http://localhost:3000/api/v1/users?page=2"
+        );
+        assert!(rebase(&recorded, "localhost:3000").is_err());
+        assert_eq!(origin(&recorded), "https://api.example.com");
+    }
+
+    #[test]
+    fn http_text_round_trip() {
+        let text = "PATCH http://localhost:3000/v1/users/7?dry=1\nContent-Type: application/json\nX-Trace: a:b\n\n{\"name\":\"Bob\"}\n";
+        let request = parse_http_text(text).unwrap();
+        assert_eq!(request.method, "PATCH");
+        assert_eq!(request.url.path(), "/v1/users/7");
+        assert_eq!(request.query_string[0].name, "dry");
+        assert_eq!(request.headers[1].value, "a:b");
+        let post_data = request.post_data.as_ref().unwrap();
+        assert_eq!(post_data.mime_type, "application/json");
+        assert_eq!(post_data.text, r#"{"name":"Bob"}"#);
+        assert_eq!(to_http_text(&request), text);
+
+        let request = parse_http_text("GET http://x.test/\n").unwrap();
+        assert!(request.headers.is_empty());
+        assert!(request.post_data.is_none());
+    }
+
+    #[test]
+    fn unedited_text_replays_the_same_body() {
+        for body in ["a=1&b=2", "{\n  \"name\": \"Bob\"\n}\n", "line\n\n"] {
+            let mut request = parse_http_text("POST http://x.test/form\nContent-Type: text/plain\n\n").unwrap();
+            request.post_data = Some(har::PostData {
+                mime_type: "text/plain".to_string(),
+                params: None,
+                text: body.to_string(),
+                extra: Default::default(),
+            });
+            request.body_size = Some(body.len() as i64);
+            // Saved as is, or with the final newline an editor adds when missing.
+            let text = to_http_text(&request);
+            assert!(text.ends_with('\n'));
+            let replayed = parse_http_text(&text).unwrap();
+            assert_eq!(replayed.post_data.unwrap().text, body);
+            assert_eq!(replayed.body_size, Some(body.len() as i64));
+        }
+        // A buffer saved without the final newline keeps its last byte.
+        let request = parse_http_text("POST http://x.test/\n\nabc").unwrap();
+        assert_eq!(request.post_data.unwrap().text, "abc");
+    }
+
+    #[test]
+    fn unedited_binary_body_replays_its_bytes() {
+        let bytes = [0xff, 0x00, b'a'];
+        let mut recorded = parse_http_text("PUT http://x.test/blob\n").unwrap();
+        recorded.post_data = Some(har::PostData {
+            mime_type: "application/octet-stream".to_string(),
+            params: None,
+            text: "/wBh".to_string(),
+            extra: [("_encoding".to_string(), "base64".into())].into_iter().collect(),
+        });
+        recorded.body_size = Some(bytes.len() as i64);
+
+        let mut edited = parse_http_text(&to_http_text(&recorded)).unwrap();
+        keep_binary_body(&mut edited, &recorded);
+        assert_eq!(edited.post_data.as_ref().unwrap().bytes(), bytes);
+        assert_eq!(edited.body_size, Some(3));
+
+        let mut edited = parse_http_text("PUT http://x.test/blob\n\nnew text\n").unwrap();
+        keep_binary_body(&mut edited, &recorded);
+        assert!(!edited.post_data.as_ref().unwrap().is_base64());
+        assert_eq!(edited.post_data.unwrap().bytes(), b"new text");
+    }
+
+    #[test]
+    fn invalid_http_text_is_an_error() {
+        assert!(parse_http_text("").is_err());
+        assert!(parse_http_text("GET\n").is_err());
+        assert!(parse_http_text("GET /relative\n").is_err());
+        assert!(parse_http_text("GET http://x.test/\nno colon\n").is_err());
+    }
+
+    #[tokio::test]
+    async fn send_records_the_new_response() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let n = stream.read(&mut buf).await.unwrap();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&buf[..n]).into_owned()
+        });
+
+        let request = parse_http_text(&format!(
+            "POST http://{}/items\n:authority: api.example.com\nHost: api.example.com\nContent-Length: 999\n\nabc",
+            addr
+        ))
+        .unwrap();
+        let entry = send(request).await;
+        let sent = server.await.unwrap();
+
+        assert_eq!(entry.response.status, 200);
+        assert_eq!(entry.response.content.text.as_deref(), Some("ok"));
+        assert!(sent.contains(&format!("host: {}\r\n", addr)), "{}", sent);
+        assert!(sent.contains("content-length: 3\r\n"), "{}", sent);
+        assert!(sent.ends_with("abc"));
+    }
+}
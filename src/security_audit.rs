@@ -0,0 +1,339 @@
+use crate::har;
+
+/// HSTS max-age below this (180 days) is flagged as short.
+pub const MIN_HSTS_MAX_AGE: u64 = 180 * 24 * 60 * 60;
+
+/// Outcome of one header check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Weak,
+    Missing,
+}
+
+/// One security header and what is wrong with it, if anything.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub header: &'static str,
+    pub value: Option<String>,
+    pub status: Status,
+    pub notes: Vec<String>,
+}
+
+/// One CSP directive, e.g. `script-src 'self' 'unsafe-inline'`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Directive {
+    pub name: String,
+    pub sources: Vec<String>,
+    pub weaknesses: Vec<String>,
+}
+
+/// Security header audit of a document response.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Audit {
+    pub checks: Vec<Check>,
+    /// Directives of the enforced (or else report-only) policy.
+    pub csp: Vec<Directive>,
+    /// Browser's `_securityState` for the entry (`secure`, `insecure`, …).
+    pub security_state: Option<String>,
+}
+
+impl Audit {
+    /// Checks that did not pass.
+    pub fn problems(&self) -> usize {
+        self.checks.iter().filter(|c| c.status != Status::Pass).count()
+    }
+}
+
+/// Whether the response is an HTML document, the kind of response these
+/// headers protect.
+pub fn is_document(entry: &har::Entry) -> bool {
+    let mime = entry
+        .response
+        .header("content-type")
+        .or(entry.response.content.mime_type.as_deref())
+        .unwrap_or_default()
+        .to_lowercase();
+    mime.starts_with("text/html") || mime.starts_with("application/xhtml+xml")
+}
+
+/// Audit the security headers of a document response; `None` for other responses.
+pub fn audit(entry: &har::Entry) -> Option<Audit> {
+    if !is_document(entry) {
+        return None;
+    }
+    let response = &entry.response;
+    let header = |name: &str| response.header(name).map(str::to_string);
+    let https = entry.request.url.scheme() == "https";
+
+    let enforced = header("content-security-policy");
+    let report_only = header("content-security-policy-report-only");
+    let csp = enforced
+        .as_deref()
+        .or(report_only.as_deref())
+        .map(parse_csp)
+        .unwrap_or_default();
+
+    let checks = vec![
+        check_csp(enforced, report_only.is_some(), &csp),
+        check_hsts(header("strict-transport-security"), https),
+        check_content_type_options(header("x-content-type-options")),
+        check_framing(header("x-frame-options"), &csp),
+        check_referrer_policy(header("referrer-policy")),
+        check_permissions_policy(header("permissions-policy"), header("feature-policy").is_some()),
+    ];
+    Some(Audit {
+        checks,
+        csp,
+        security_state: entry.security_state.clone(),
+    })
+}
+
+fn check(header: &'static str, value: Option<String>, notes: Vec<String>, missing: bool) -> Check {
+    let status = match (&value, notes.is_empty()) {
+        _ if missing => Status::Missing,
+        (None, _) => Status::Missing,
+        (Some(_), true) => Status::Pass,
+        (Some(_), false) => Status::Weak,
+    };
+    Check {
+        header,
+        value,
+        status,
+        notes,
+    }
+}
+
+/// Split a policy into directives and flag the weak ones.
+pub fn parse_csp(policy: &str) -> Vec<Directive> {
+    policy
+        .split(';')
+        .filter_map(|part| {
+            let mut tokens = part.split_whitespace();
+            let name = tokens.next()?.to_lowercase();
+            let sources: Vec<String> = tokens.map(str::to_string).collect();
+            let weaknesses = directive_weaknesses(&name, &sources);
+            Some(Directive {
+                name,
+                sources,
+                weaknesses,
+            })
+        })
+        .collect()
+}
+
+fn directive_weaknesses(name: &str, sources: &[String]) -> Vec<String> {
+    if !matches!(name, "default-src" | "script-src" | "script-src-elem" | "object-src" | "style-src") {
+        return Vec::new();
+    }
+    let has = |s: &str| sources.iter().any(|src| src.eq_ignore_ascii_case(s));
+    // With a nonce or hash, CSP Level 3 browsers ignore 'unsafe-inline'.
+    let nonce_or_hash = sources.iter().any(|s| {
+        let s = s.to_lowercase();
+        s.starts_with("'nonce-") || s.starts_with("'sha256-") || s.starts_with("'sha384-") || s.starts_with("'sha512-")
+    });
+    let scripts = name != "style-src";
+    let mut weaknesses = Vec::new();
+    if has("'unsafe-inline'") && !nonce_or_hash {
+        weaknesses.push("'unsafe-inline' allows injected inline code".to_string());
+    }
+    if scripts && has("'unsafe-eval'") {
+        weaknesses.push("'unsafe-eval' allows eval()".to_string());
+    }
+    if has("*") {
+        weaknesses.push("* allows any host".to_string());
+    }
+    if scripts {
+        for scheme in ["http:", "https:", "data:"] {
+            if has(scheme) {
+                weaknesses.push(format!("{} allows any source with that scheme", scheme));
+            }
+        }
+    }
+    weaknesses
+}
+
+fn check_csp(enforced: Option<String>, report_only: bool, csp: &[Directive]) -> Check {
+    let mut notes = Vec::new();
+    if enforced.is_none() && report_only {
+        notes.push("only Content-Security-Policy-Report-Only is set; nothing is enforced".to_string());
+    }
+    let directive = |name: &str| csp.iter().find(|d| d.name == name);
+    if enforced.is_some() {
+        if directive("script-src").or(directive("default-src")).is_none() {
+            notes.push("no script-src or default-src: scripts are unrestricted".to_string());
+        }
+        if directive("object-src").or(directive("default-src")).is_none() {
+            notes.push("no object-src or default-src: plugins are unrestricted".to_string());
+        }
+        if csp.iter().any(|d| !d.weaknesses.is_empty()) {
+            notes.push("weak directives (see below)".to_string());
+        }
+    }
+    let missing = enforced.is_none();
+    check("Content-Security-Policy", enforced, notes, missing)
+}
+
+fn check_hsts(value: Option<String>, https: bool) -> Check {
+    let mut notes = Vec::new();
+    if !https {
+        notes.push("served over plain HTTP, where browsers ignore HSTS".to_string());
+        let missing = value.is_none();
+        return check("Strict-Transport-Security", value, notes, missing);
+    }
+    if let Some(value) = &value {
+        let directives: Vec<String> = value.split(';').map(|d| d.trim().to_lowercase()).collect();
+        let max_age = directives
+            .iter()
+            .find_map(|d| d.strip_prefix("max-age="))
+            .and_then(|v| v.trim_matches('"').parse::<u64>().ok());
+        match max_age {
+            None => notes.push("no valid max-age".to_string()),
+            Some(0) => notes.push("max-age=0 removes the HSTS policy".to_string()),
+            Some(age) if age < MIN_HSTS_MAX_AGE => notes.push(format!(
+                "max-age is {} days; at least 180 (ideally 365) is recommended",
+                age / 86_400
+            )),
+            Some(_) => {}
+        }
+        if !directives.iter().any(|d| d == "includesubdomains") {
+            notes.push("no includeSubDomains".to_string());
+        }
+    }
+    check("Strict-Transport-Security", value, notes, false)
+}
+
+fn check_content_type_options(value: Option<String>) -> Check {
+    let mut notes = Vec::new();
+    if value.as_deref().is_some_and(|v| !v.trim().eq_ignore_ascii_case("nosniff")) {
+        notes.push("the only valid value is nosniff".to_string());
+    }
+    check("X-Content-Type-Options", value, notes, false)
+}
+
+fn check_framing(value: Option<String>, csp: &[Directive]) -> Check {
+    let frame_ancestors = csp.iter().find(|d| d.name == "frame-ancestors");
+    let mut notes = Vec::new();
+    if let Some(directive) = frame_ancestors {
+        if directive.sources.iter().any(|s| s == "*") {
+            notes.push("frame-ancestors * allows framing by any site".to_string());
+        }
+        let value = Some(format!("frame-ancestors {}", directive.sources.join(" ")));
+        return check("X-Frame-Options / frame-ancestors", value, notes, false);
+    }
+    match value.as_deref().map(|v| v.trim().to_uppercase()) {
+        Some(v) if v == "DENY" || v == "SAMEORIGIN" => {}
+        Some(v) if v.starts_with("ALLOW-FROM") => {
+            notes.push("ALLOW-FROM is obsolete and ignored; use CSP frame-ancestors".to_string())
+        }
+        Some(_) => notes.push("expected DENY or SAMEORIGIN".to_string()),
+        None => {}
+    }
+    check("X-Frame-Options / frame-ancestors", value, notes, false)
+}
+
+fn check_referrer_policy(value: Option<String>) -> Check {
+    let mut notes = Vec::new();
+    // The last recognised token wins; fallbacks come first.
+    let policy = value
+        .as_deref()
+        .and_then(|v| v.rsplit(',').next().map(|p| p.trim().to_lowercase()));
+    if let Some(policy) = policy {
+        if matches!(policy.as_str(), "unsafe-url" | "no-referrer-when-downgrade") {
+            notes.push(format!("{} sends the full URL to other origins", policy));
+        }
+    }
+    check("Referrer-Policy", value, notes, false)
+}
+
+fn check_permissions_policy(value: Option<String>, feature_policy: bool) -> Check {
+    let mut notes = Vec::new();
+    if value.is_none() && feature_policy {
+        notes.push("only the deprecated Feature-Policy is set".to_string());
+    }
+    check("Permissions-Policy", value, notes, false)
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(url: &str, headers: &[(&str, &str)]) -> har::Entry {
+        let mut entry = har::Entry::test("GET", url, 200).with_response_headers(headers);
+        entry.response.content.mime_type = Some("text/html; charset=utf-8".to_string());
+        entry.security_state = Some("secure".to_string());
+        entry
+    }
+
+    fn statuses(audit: &Audit) -> Vec<Status> {
+        audit.checks.iter().map(|c| c.status).collect()
+    }
+
+    #[test]
+    fn well_configured_document_passes() {
+        let entry = document(
+            "https://x.test/",
+            &[
+                ("Content-Security-Policy", "default-src 'self'; script-src 'self' 'nonce-abc' 'unsafe-inline'; frame-ancestors 'none'"),
+                ("Strict-Transport-Security", "max-age=63072000; includeSubDomains; preload"),
+                ("X-Content-Type-Options", "nosniff"),
+                ("Referrer-Policy", "strict-origin-when-cross-origin"),
+                ("Permissions-Policy", "camera=()"),
+            ],
+        );
+        let audit = audit(&entry).unwrap();
+        assert_eq!(statuses(&audit), vec![Status::Pass; 6], "{:#?}", audit.checks);
+        assert_eq!(audit.problems(), 0);
+        assert_eq!(audit.checks[3].value.as_deref(), Some("frame-ancestors 'none'"));
+        assert_eq!(audit.csp.len(), 3);
+        assert_eq!(audit.security_state.as_deref(), Some("secure"));
+    }
+
+    #[test]
+    fn weaknesses_are_flagged() {
+        let entry = document(
+            "https://x.test/",
+            &[
+                ("Content-Security-Policy", "script-src 'self' 'unsafe-inline' 'unsafe-eval' https:"),
+                ("Strict-Transport-Security", "max-age=86400"),
+                ("X-Frame-Options", "ALLOW-FROM https://a.test"),
+                ("Referrer-Policy", "no-referrer, unsafe-url"),
+            ],
+        );
+        let audit = audit(&entry).unwrap();
+        assert_eq!(
+            statuses(&audit),
+            vec![Status::Weak, Status::Weak, Status::Missing, Status::Weak, Status::Weak, Status::Missing]
+        );
+        let script_src = &audit.csp[0];
+        assert_eq!(script_src.weaknesses.len(), 3, "{:?}", script_src.weaknesses);
+        assert_eq!(audit.checks[0].notes[0], "no object-src or default-src: plugins are unrestricted");
+        assert_eq!(
+            audit.checks[1].notes,
+            vec!["max-age is 1 days; at least 180 (ideally 365) is recommended", "no includeSubDomains"]
+        );
+        assert_eq!(audit.problems(), 6);
+    }
+
+    #[test]
+    fn report_only_and_plain_http() {
+        let entry = document(
+            "http://x.test/",
+            &[("Content-Security-Policy-Report-Only", "default-src 'self'")],
+        );
+        let audit = audit(&entry).unwrap();
+        assert_eq!(audit.checks[0].status, Status::Missing);
+        assert!(audit.checks[0].notes[0].contains("nothing is enforced"));
+        assert_eq!(audit.csp[0].name, "default-src");
+        assert!(audit.checks[1].notes[0].contains("plain HTTP"));
+    }
+
+    #[test]
+    fn only_documents_are_audited() {
+        let mut entry = document("https://x.test/app.js", &[]);
+        entry.response.content.mime_type = Some("application/javascript".to_string());
+        assert!(audit(&entry).is_none());
+    }
+}
@@ -1,23 +1,31 @@
 use crate::app::App;
 use crate::event::EventHandler;
+use crate::image_preview::{self, ImageProtocol};
 use crate::ui;
 use anyhow::Context;
 use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
 use ratatui::backend::Backend;
+use ratatui::layout::Rect;
 use ratatui::Terminal;
-use std::io;
+use std::io::{self, Write};
 use std::panic;
 
 #[derive(Debug)]
 pub struct Tui<B: Backend> {
     terminal: Terminal<B>,
     pub events: EventHandler,
+    /// Entry index and cells of the image currently painted by a graphics protocol.
+    placed_image: Option<(usize, Rect)>,
 }
 
 impl<B: Backend> Tui<B> {
     pub fn new(terminal: Terminal<B>, events: EventHandler) -> Self {
-        Self { terminal, events }
+        Self {
+            terminal,
+            events,
+            placed_image: None,
+        }
     }
 
     pub fn init(&mut self) -> anyhow::Result<()> {
@@ -46,10 +54,45 @@ impl<B: Backend> Tui<B> {
             self.clear()?;
         }
         self.terminal.draw(|frame| ui::render(app, frame))?;
+        self.sync_image(app)
+    }
+
+    /// Paint, move or remove the graphics-protocol image after a frame is drawn.
+    /// Images live outside ratatui's buffer, so they are only re-sent when the
+    /// entry or the reserved cells change.
+    fn sync_image(&mut self, app: &mut App) -> anyhow::Result<()> {
+        let wanted = app
+            .image_placement
+            .get()
+            .map(|rect| (app.get_entry_index(), rect));
+        if wanted == self.placed_image {
+            return Ok(());
+        }
+
+        if self.placed_image.take().is_some() {
+            if app.image_protocol == ImageProtocol::Kitty {
+                write!(io::stderr(), "{}", image_preview::kitty_clear_sequence())?;
+            } else {
+                // iTerm2/sixel pixels stay on screen until the cells are rewritten.
+                self.clear()?;
+                self.terminal.draw(|frame| ui::render(app, frame))?;
+            }
+        }
+
+        if let (Some((_, rect)), Some(preview)) = (wanted, &app.image_preview) {
+            if let Some(seq) = preview.encode(app.image_protocol, rect.width, rect.height) {
+                let mut out = io::stderr();
+                crossterm::execute!(out, crossterm::cursor::MoveTo(rect.x, rect.y))?;
+                write!(out, "{}", seq)?;
+                out.flush()?;
+                self.placed_image = wanted;
+            }
+        }
         Ok(())
     }
 
     pub fn clear(&mut self) -> anyhow::Result<()> {
+        self.placed_image = None;
         self.terminal
             .clear()
             .context("failed to clear terminal screen")?;
@@ -1,7 +1,21 @@
-use crate::app::{ActiveFocus, App, CookieInfo, HeaderInfo, TabBarState, TableItem};
+use crate::app::{ActiveFocus, App, BodyView, CookieInfo, HeaderInfo, Replay, TabBarState, TableItem};
+use crate::entry_diff::{Cell as DiffCell, Change, DiffRow, EntryDiff};
+use crate::caching;
+use crate::cors;
+use crate::duplicates;
+use crate::endpoints;
+use crate::hexdump;
+use crate::image_preview::ImagePreview;
+use crate::json_tree;
+use crate::har;
+use crate::jwt;
+use crate::redirects;
+use crate::secret_scan;
+use crate::security_audit;
 use ratatui::{prelude::*, widgets::*};
 
 pub fn render(app: &mut App, frame: &mut Frame) {
+    app.image_placement.set(None);
     let main_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Fill(1), Constraint::Fill(1)])
@@ -12,7 +26,11 @@ pub fn render(app: &mut App, frame: &mut Frame) {
 }
 
 pub fn render_table(app: &mut App, area: Rect, buf: &mut Buffer) {
-    let (table_area, search_area) = if app.search_mode || app.search_active {
+    let (table_area, search_area) = if app.search_mode
+        || app.search_active
+        || app.replay_input.is_some()
+        || app.status_message.is_some()
+    {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Fill(1), Constraint::Length(1)])
@@ -27,10 +45,28 @@ pub fn render_table(app: &mut App, area: Rect, buf: &mut Buffer) {
     table.render(table_area, buf, &mut state);
 
     if let Some(sb_area) = search_area {
-        render_search_bar(app, sb_area, buf);
+        match (&app.replay_input, &app.status_message) {
+            (Some(input), _) => render_replay_prompt(app, input, sb_area, buf),
+            (None, Some(message)) => {
+                Paragraph::new(Span::styled(message.as_str(), Style::default().fg(Color::Green))).render(sb_area, buf)
+            }
+            (None, None) => render_search_bar(app, sb_area, buf),
+        }
     }
 }
 
+fn render_replay_prompt(app: &App, input: &str, area: Rect, buf: &mut Buffer) {
+    let line = Line::from(vec![
+        Span::styled(
+            format!("Replay #{} to: ", app.get_entry_index() + 1),
+            Style::default().fg(Color::Yellow).bold(),
+        ),
+        Span::raw(format!("{}▏", input)),
+        Span::styled("  Enter send · Esc cancel", Style::default().fg(Color::DarkGray)),
+    ]);
+    Paragraph::new(line).render(area, buf);
+}
+
 pub fn render_preview(app: &mut App, area: Rect, buf: &mut Buffer) {
     let preview = PreviewWidget::init(app);
     preview.render(area, buf);
@@ -53,9 +89,10 @@ fn render_search_bar(app: &App, area: Rect, buf: &mut Buffer) {
     let cursor = if app.search_mode { "▏" } else { "" };
     let left_width = area.width.saturating_sub(right_width);
 
-    let right_style = if app.search_error {
-        Style::default().fg(Color::LightRed)
-    } else if match_count == 0 && (app.search_active || (!app.search_query.is_empty() && app.search_mode)) {
+    let right_style = if app.search_error
+        || (match_count == 0
+            && (app.search_active || (!app.search_query.is_empty() && app.search_mode)))
+    {
         Style::default().fg(Color::LightRed)
     } else {
         Style::default().fg(Color::LightGreen)
@@ -165,6 +202,15 @@ pub struct EntriesTable<'a> {
     match_count: usize,
     total_count: usize,
     search_regex: Option<regex::Regex>,
+    /// Display position of the entry marked for diffing.
+    marked_row: Option<usize>,
+    /// `vs <baseline>: <summary>` when comparing against another capture.
+    baseline_summary: Option<String>,
+    /// Set while entries are still arriving, e.g. `recording`.
+    live: Option<&'static str>,
+    /// Number of files merged into the timeline; a Source column is shown above one.
+    source_count: usize,
+    duplicates_only: bool,
 }
 
 impl<'a> EntriesTable<'a> {
@@ -181,9 +227,19 @@ impl<'a> EntriesTable<'a> {
             table_offset: app.table_offset,
             selected_index: app.get_index(),
             search_active: app.search_active,
+            duplicates_only: app.duplicates_only,
             match_count: app.display_entry_indices.len(),
             total_count: app.table_items.len(),
             search_regex: app.search_regex.clone(),
+            marked_row: app
+                .marked_entry
+                .and_then(|m| app.display_entry_indices.iter().position(|&i| i == m)),
+            baseline_summary: app
+                .baseline
+                .as_ref()
+                .map(|b| format!("vs {}: {}", b.name, b.summary)),
+            live: app.live_entries.as_ref().map(|_| app.live_label),
+            source_count: app.sources.len(),
         }
     }
 }
@@ -202,17 +258,16 @@ impl<'a> StatefulWidget for EntriesTable<'a> {
             &[]
         };
 
-        let headers = Row::new(vec![
+        let show_source = self.source_count > 1;
+        let mut header_cells = vec![
             Cell::from("Status"),
             Cell::from("Method"),
             Cell::from("URL"),
             Cell::from("ContentType"),
             Cell::from("     Size  "),
             Cell::from("Timestamp"),
-        ])
-        .style(Style::default().bold().underlined());
-
-        let widths = [
+        ];
+        let mut widths = vec![
             Constraint::Length(6),
             Constraint::Length(7),
             Constraint::Fill(1),
@@ -220,9 +275,26 @@ impl<'a> StatefulWidget for EntriesTable<'a> {
             Constraint::Length(10),
             Constraint::Length(14),
         ];
+        if show_source {
+            let longest = self.display_items.iter().map(|i| i.source.chars().count()).max();
+            header_cells.insert(0, Cell::from("Source"));
+            widths.insert(0, Constraint::Length(longest.unwrap_or(0).clamp(6, 20) as u16));
+        }
+        let headers = Row::new(header_cells).style(Style::default().bold().underlined());
 
         let re_opt = self.search_regex.as_ref();
-        let rows: Vec<Row> = visible_items.iter().map(|item| make_row(item, re_opt)).collect();
+        let rows: Vec<Row> = visible_items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let row = make_row(item, re_opt, show_source);
+                if self.marked_row == Some(start_index + i) {
+                    row.style(Style::default().bg(Color::Indexed(238)))
+                } else {
+                    row
+                }
+            })
+            .collect();
 
         if self.selected_index >= start_index && self.selected_index < end_index {
             state.select(Some(self.selected_index - start_index));
@@ -231,10 +303,24 @@ impl<'a> StatefulWidget for EntriesTable<'a> {
         }
         *state.offset_mut() = 0;
 
-        let title = if self.search_active {
-            format!(" {}/{} matches ", self.match_count, self.total_count)
-        } else {
+        let mut title_parts = Vec::new();
+        if let Some(label) = self.live {
+            title_parts.push(format!("● {} ({})", label, self.total_count));
+        }
+        if self.source_count > 1 {
+            title_parts.push(format!("{} files", self.source_count));
+        }
+        if self.duplicates_only {
+            title_parts.push("duplicates only".to_string());
+        }
+        if self.search_active || self.duplicates_only {
+            title_parts.push(format!("{}/{} matches", self.match_count, self.total_count));
+        }
+        title_parts.extend(self.baseline_summary);
+        let title = if title_parts.is_empty() {
             String::new()
+        } else {
+            format!(" {} ", title_parts.join(" — "))
         };
 
         let table = Table::new(rows, &widths)
@@ -268,7 +354,7 @@ pub struct PreviewWidget<'a> {
 impl<'a> PreviewWidget<'a> {
     pub fn init(app: &'a App) -> Self {
         Self {
-            tabbar_state: app.tabbar_state.clone(),
+            tabbar_state: app.tabbar_state,
             app,
         }
     }
@@ -284,41 +370,49 @@ impl<'a> Widget for PreviewWidget<'a> {
             .constraints([Constraint::Length(1), Constraint::Fill(1)])
             .split(area);
 
+        // Keep in sync with `App::tab_zones`, which maps mouse clicks to tabs.
         let tab_row = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Fill(1), Constraint::Length(12)])
+            .constraints(
+                std::iter::once(Constraint::Fill(1))
+                    .chain(TabBarState::SIDE.iter().map(|(_, slot)| Constraint::Length(*slot))),
+            )
             .split(layout[0]);
 
-        let main_tabs = Tabs::new(vec![
-            " [1] Headers ",
-            " [2] Cookies ",
-            " [3] Request ",
-            " [4] Response ",
-        ])
-        .select(if self.tabbar_state == TabBarState::Help {
-            usize::MAX
-        } else {
-            self.tabbar_state.to_index()
-        })
-        .padding(" ", " ");
+        let main_tabs = Tabs::new(TabBarState::MAIN.iter().map(|tab| tab.to_string()).collect::<Vec<_>>())
+            .select(
+                TabBarState::MAIN
+                    .iter()
+                    .position(|tab| *tab == self.tabbar_state)
+                    .unwrap_or(usize::MAX),
+            )
+            .padding(" ", " ");
 
         Widget::render(main_tabs, tab_row[0], buf);
 
-        let help_style = if self.tabbar_state == TabBarState::Help {
-            Style::default().reversed()
-        } else {
-            Style::default()
-        };
-        let help_label = Paragraph::new(Span::styled(" [?] Help ", help_style))
-            .alignment(Alignment::Right);
-        Widget::render(help_label, tab_row[1], buf);
+        for ((tab, _), slot) in TabBarState::SIDE.iter().zip(&tab_row[1..]) {
+            if *tab == TabBarState::Diff && !self.app.diff_tab_shown() {
+                continue;
+            }
+            let style = if self.tabbar_state == *tab {
+                Style::default().reversed()
+            } else {
+                Style::default()
+            };
+            let label = Paragraph::new(Span::styled(tab.to_string(), style)).alignment(Alignment::Right);
+            Widget::render(label, *slot, buf);
+        }
 
         match self.tabbar_state {
             TabBarState::Headers => HeaderPreview::init(self.app).render(layout[1], buf),
             TabBarState::Cookies => CookiePreview::init(self.app).render(layout[1], buf),
             TabBarState::Request => RequestPreview::init(self.app).render(layout[1], buf),
             TabBarState::Response => ResponsePreview::init(self.app).render(layout[1], buf),
+            TabBarState::Inspect => InspectPreview::init(self.app).render(layout[1], buf),
+            TabBarState::Report => ReportPreview::init(self.app).render(layout[1], buf),
             TabBarState::Help => HelpPreview::init(self.app).render(layout[1], buf),
+            TabBarState::Diff => DiffPreview::init(self.app).render(layout[1], buf),
+            TabBarState::Secrets => SecretsPreview::init(self.app).render(layout[1], buf),
         }
     }
 }
@@ -327,6 +421,8 @@ impl<'a> Widget for PreviewWidget<'a> {
 
 struct HeaderPreview {
     header_info: Option<HeaderInfo>,
+    tokens: Vec<jwt::Found>,
+    requested_at: chrono::DateTime<chrono::Utc>,
     scroll: u16,
     active_focus: ActiveFocus,
     search_regex: Option<regex::Regex>,
@@ -336,7 +432,15 @@ impl HeaderPreview {
     pub fn init(app: &App) -> Self {
         Self {
             header_info: app.to_header_info(app.get_entry_index()),
-            scroll: app.scroll,
+            tokens: app.entry_tokens.clone(),
+            requested_at: app
+                .har
+                .log
+                .entries
+                .get(app.get_entry_index())
+                .map(jwt::request_time)
+                .unwrap_or_else(chrono::Utc::now),
+            scroll: clamp_scroll(app.scroll),
             active_focus: app.active_focus,
             search_regex: app.search_regex.clone(),
         }
@@ -393,6 +497,20 @@ impl Widget for HeaderPreview {
                         Span::raw(value.clone()),
                     ]));
                 }
+                if !self.tokens.is_empty() {
+                    v.push(Line::raw(""));
+                    v.push(Line::from(vec![Span::styled(
+                        "JSON Web Tokens",
+                        Style::default().bold().underlined(),
+                    )]));
+                }
+                for found in &self.tokens {
+                    v.push(Line::from(Span::styled(
+                        found.location.clone(),
+                        Style::default().fg(Color::Blue),
+                    )));
+                    v.extend(jwt_lines(&found.jwt, self.requested_at, "  "));
+                }
                 v
             };
 
@@ -421,6 +539,7 @@ impl Widget for HeaderPreview {
 
 pub struct CookiePreview {
     cookie_info: Option<CookieInfo>,
+    requested_at: chrono::DateTime<chrono::Utc>,
     scroll: u16,
     active_focus: ActiveFocus,
     search_regex: Option<regex::Regex>,
@@ -430,7 +549,14 @@ impl CookiePreview {
     pub fn init(app: &App) -> Self {
         Self {
             cookie_info: app.to_cookie_info(app.get_entry_index()),
-            scroll: app.scroll,
+            requested_at: app
+                .har
+                .log
+                .entries
+                .get(app.get_entry_index())
+                .map(jwt::request_time)
+                .unwrap_or_else(chrono::Utc::now),
+            scroll: clamp_scroll(app.scroll),
             active_focus: app.active_focus,
             search_regex: app.search_regex.clone(),
         }
@@ -453,6 +579,9 @@ impl Widget for CookiePreview {
                         Span::styled(format!("{}: ", name), Style::default().fg(Color::Blue)),
                         Span::raw(value.clone()),
                     ]));
+                    if let Some(token) = jwt::decode(value) {
+                        v.extend(jwt_lines(&token, self.requested_at, "  "));
+                    }
                 }
                 v.push(Line::raw(""));
                 v.push(Line::from(vec![Span::styled(
@@ -467,6 +596,9 @@ impl Widget for CookiePreview {
                         Span::styled(format!("{}: ", name), Style::default().fg(Color::Blue)),
                         Span::raw(value.clone()),
                     ]));
+                    if let Some(token) = jwt::decode(value) {
+                        v.extend(jwt_lines(&token, self.requested_at, "  "));
+                    }
                 }
                 v
             };
@@ -492,11 +624,582 @@ impl Widget for CookiePreview {
     }
 }
 
+/// A decoded JWT: warnings, then the header and payload as pretty JSON with
+/// time claims annotated relative to the request.
+fn jwt_lines(token: &jwt::Jwt, requested_at: chrono::DateTime<chrono::Utc>, indent: &str) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut lines = vec![Line::from(Span::styled(
+        format!("{}JWT, alg {}", indent, token.alg().unwrap_or("?")),
+        Style::default().fg(Color::Magenta),
+    ))];
+    for warning in token.warnings(requested_at) {
+        lines.push(Line::from(Span::styled(
+            format!("{}⚠ {}", indent, warning),
+            Style::default().fg(Color::LightRed).bold(),
+        )));
+    }
+    lines.push(Line::from(Span::styled(format!("{}Header", indent), dim)));
+    for line in token.header_lines() {
+        lines.push(Line::raw(format!("{}  {}", indent, line)));
+    }
+    lines.push(Line::from(Span::styled(format!("{}Payload", indent), dim)));
+    for (line, note) in token.payload_lines(requested_at) {
+        let mut spans = vec![Span::raw(format!("{}  {}", indent, line))];
+        if let Some(note) = note {
+            spans.push(Span::styled(format!("  // {}", note), Style::default().fg(Color::Cyan)));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+// ── InspectPreview ────────────────────────────────────────────────────────────
+
+/// Analyses of the selected entry, one section each.
+pub struct InspectPreview {
+    lines: Vec<Line<'static>>,
+    scroll: u16,
+    active_focus: ActiveFocus,
+    search_regex: Option<regex::Regex>,
+}
+
+impl InspectPreview {
+    pub fn init(app: &App) -> Self {
+        let entries = &app.har.log.entries;
+        let index = app.get_entry_index();
+        let mut lines = entries.get(index).map(security_lines).unwrap_or_default();
+        if let Some(entry) = entries.get(index) {
+            lines.push(Line::raw(""));
+            lines.extend(caching_lines(entry));
+        }
+        if let Some(chain) = &app.entry_redirects {
+            lines.push(Line::raw(""));
+            lines.extend(redirect_lines(entries, chain, index));
+        }
+        if let Some(exchange) = app.entry_cors {
+            lines.push(Line::raw(""));
+            lines.extend(cors_lines(entries, exchange));
+        }
+        Self {
+            lines,
+            scroll: clamp_scroll(app.scroll),
+            active_focus: app.active_focus,
+            search_regex: app.search_regex.clone(),
+        }
+    }
+}
+
+impl Widget for InspectPreview {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let lines = apply_highlights(self.lines.into_iter(), self.search_regex.as_ref());
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Inspect")
+                    .border_style(if self.active_focus == ActiveFocus::Preview {
+                        Style::default().fg(Color::Green)
+                    } else {
+                        Style::default().fg(Color::DarkGray)
+                    }),
+            )
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0));
+        Widget::render(paragraph, area, buf);
+    }
+}
+
+fn section_heading(title: &str) -> Line<'static> {
+    Line::from(Span::styled(title.to_string(), Style::default().bold().underlined()))
+}
+
+/// Security header audit of a document response, with the browser's security state.
+fn security_lines(entry: &har::Entry) -> Vec<Line<'static>> {
+    use security_audit::Status;
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut lines = vec![section_heading("Security Headers")];
+    if let Some(state) = &entry.security_state {
+        lines.push(Line::from(vec![
+            Span::raw("Security state: "),
+            Span::styled(
+                state.clone(),
+                Style::default().fg(if state == "secure" { Color::Green } else { Color::LightRed }),
+            ),
+        ]));
+    }
+    let Some(audit) = security_audit::audit(entry) else {
+        lines.push(Line::from(Span::styled(
+            "Not an HTML document; security headers are audited on document responses.",
+            dim,
+        )));
+        return lines;
+    };
+    lines[0] = match audit.problems() {
+        0 => section_heading("Security Headers — all checks pass"),
+        n => section_heading(&format!("Security Headers — {} of {} checks need attention", n, audit.checks.len())),
+    };
+
+    for check in &audit.checks {
+        let (mark, color) = match check.status {
+            Status::Pass => ("✓", Color::Green),
+            Status::Weak => ("!", Color::Yellow),
+            Status::Missing => ("✗", Color::LightRed),
+        };
+        let mut spans = vec![
+            Span::styled(format!("{} ", mark), Style::default().fg(color).bold()),
+            Span::styled(check.header.to_string(), Style::default().fg(Color::Blue)),
+        ];
+        match &check.value {
+            Some(value) => spans.push(Span::raw(format!(": {}", value))),
+            None => spans.push(Span::styled(" (missing)", Style::default().fg(color))),
+        }
+        lines.push(Line::from(spans));
+        for note in &check.notes {
+            lines.push(Line::from(Span::styled(format!("    {}", note), Style::default().fg(color))));
+        }
+    }
+
+    if !audit.csp.is_empty() {
+        lines.push(Line::raw(""));
+        lines.push(section_heading("Content-Security-Policy Directives"));
+        for directive in &audit.csp {
+            let style = if directive.weaknesses.is_empty() {
+                Style::default()
+            } else {
+                Style::default().fg(Color::Yellow)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {}", directive.name), Style::default().fg(Color::Blue)),
+                Span::styled(format!(" {}", directive.sources.join(" ")), style),
+            ]));
+            for weakness in &directive.weaknesses {
+                lines.push(Line::from(Span::styled(
+                    format!("      ⚠ {}", weakness),
+                    Style::default().fg(Color::Yellow),
+                )));
+            }
+        }
+    }
+    lines
+}
+
+/// Cacheability, freshness and revalidation of the response.
+fn caching_lines(entry: &har::Entry) -> Vec<Line<'static>> {
+    let analysis = caching::analyze(entry);
+    let dim = Style::default().fg(Color::DarkGray);
+    let label = Style::default().fg(Color::Blue);
+    let heading = match (&analysis.lifetime, analysis.cacheable) {
+        (_, false) => "Caching — not cacheable".to_string(),
+        (Some(lifetime), true) if lifetime.seconds > 0 => format!(
+            "Caching — fresh for {} ({})",
+            jwt::human_duration(chrono::Duration::seconds(lifetime.seconds as i64)),
+            lifetime.source
+        ),
+        (_, true) => "Caching — cacheable, revalidated before reuse".to_string(),
+    };
+    let mut lines = vec![section_heading(&heading)];
+
+    let response = &entry.response;
+    for name in ["Cache-Control", "Expires", "ETag", "Last-Modified", "Vary", "Age", "Date"] {
+        if let Some(value) = response.header(name) {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{}: ", name), label),
+                Span::raw(value.to_string()),
+            ]));
+        }
+    }
+    if let Some(lifetime) = &analysis.lifetime {
+        let left = if analysis.age > 0 {
+            format!(
+                ", {} left after Age",
+                jwt::human_duration(chrono::Duration::seconds(analysis.remaining() as i64))
+            )
+        } else {
+            String::new()
+        };
+        lines.push(Line::from(vec![
+            Span::styled("Freshness lifetime: ", label),
+            Span::raw(format!(
+                "{} ({}){}",
+                jwt::human_duration(chrono::Duration::seconds(lifetime.seconds as i64)),
+                lifetime.source,
+                left
+            )),
+        ]));
+    }
+
+    let conditional = match analysis.conditional {
+        caching::Conditional::No => None,
+        caching::Conditional::NotModified => Some((
+            "conditional request answered 304 Not Modified: the cached body was reused",
+            Color::Green,
+        )),
+        caching::Conditional::Modified => Some((
+            "conditional request, but the full body was sent again",
+            Color::Yellow,
+        )),
+    };
+    if let Some((text, color)) = conditional {
+        lines.push(Line::from(Span::styled(format!("✓ {}", text), Style::default().fg(color))));
+    } else if analysis.cacheable && !analysis.validators.is_empty() && analysis.from_cache.is_none() {
+        let names: Vec<&str> = analysis.validators.iter().map(|(name, _)| *name).collect();
+        lines.push(Line::from(Span::styled(
+            format!("Not a conditional request; {} would allow a 304 once stale", names.join(" and ")),
+            dim,
+        )));
+    }
+    if let Some(from) = &analysis.from_cache {
+        lines.push(Line::from(Span::styled(
+            format!("✓ served from the {} cache", from),
+            Style::default().fg(Color::Green),
+        )));
+    }
+    let note_style = if analysis.cacheable {
+        dim
+    } else {
+        Style::default().fg(Color::Yellow)
+    };
+    for note in &analysis.notes {
+        lines.push(Line::from(Span::styled(format!("  {}", note), note_style)));
+    }
+    lines
+}
+
+/// Every hop of a redirect chain with its status, timing and `Location`.
+fn redirect_lines(entries: &[har::Entry], chain: &redirects::Chain, selected: usize) -> Vec<Line<'static>> {
+    let warning = Style::default().fg(Color::LightRed);
+    let dim = Style::default().fg(Color::DarkGray);
+    let hops = chain.hops.len();
+    let mut heading = format!(
+        "Redirect Chain — {} request{}, {:.0} ms",
+        hops,
+        if hops == 1 { "" } else { "s" },
+        chain.time(entries)
+    );
+    if chain.loop_at.is_some() {
+        heading.push_str(" — loop");
+    }
+    let mut lines = vec![section_heading(&heading)];
+
+    let number_width = chain.hops.iter().map(|i| (i + 1).to_string().len()).max().unwrap_or(0) + 1;
+    for (position, &index) in chain.hops.iter().enumerate() {
+        let entry = &entries[index];
+        let status = entry.response.status;
+        let status_color = match status {
+            300..=399 => Color::Cyan,
+            200..=299 => Color::Green,
+            _ => Color::LightRed,
+        };
+        let marker = if index == selected { "▶ " } else { "  " };
+        lines.push(Line::from(vec![
+            Span::styled(marker, Style::default().fg(Color::Yellow)),
+            Span::styled(format!("{:<width$} ", format!("#{}", index + 1), width = number_width), dim),
+            Span::styled(format!("{} ", status), Style::default().fg(status_color).bold()),
+            Span::styled(format!("{:>6.0} ms  ", entry.time), dim),
+            Span::raw(entry.request.url.to_string()),
+        ]));
+        if let Some(location) = entry.response.header("location") {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled("Location: ", Style::default().fg(Color::Blue)),
+                Span::raw(location.to_string()),
+            ]));
+        }
+        if chain.downgrades.contains(&position) {
+            lines.push(Line::from(Span::styled("    ⚠ HTTPS → HTTP downgrade", warning)));
+        }
+        if chain.loop_at == Some(position) {
+            lines.push(Line::from(Span::styled(
+                "    ⚠ Loop: this URL was already requested earlier in the chain",
+                warning,
+            )));
+        }
+    }
+    if let Some(target) = &chain.unfollowed {
+        lines.push(Line::from(Span::styled(format!("  → {} (not in the capture)", target), dim)));
+        if chain.loop_at == Some(hops) {
+            lines.push(Line::from(Span::styled(
+                "    ⚠ Loop: this URL was already requested earlier in the chain",
+                warning,
+            )));
+        }
+    }
+    lines
+}
+
+/// A preflight and its request side by side, with the browser's verdict.
+fn cors_lines(entries: &[har::Entry], exchange: cors::Exchange) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let preflight = exchange.preflight.and_then(|i| entries.get(i));
+    let actual = exchange.actual.and_then(|i| entries.get(i));
+    let verdict = cors::verdict(preflight, actual);
+    let mut lines = vec![section_heading(if verdict.allowed {
+        "CORS — allowed"
+    } else {
+        "CORS — blocked"
+    })];
+
+    let describe = |index: Option<usize>, entry: Option<&har::Entry>| match (index, entry) {
+        (Some(i), Some(e)) => format!("#{} {} {}", i + 1, e.request.method, e.response.status),
+        _ => "not captured".to_string(),
+    };
+    let rows = cors::header_rows(preflight, actual);
+    let name_width = rows.iter().map(|(name, _, _)| name.len()).max().unwrap_or(0);
+    let mut cells: Vec<(String, String, String)> = vec![(
+        String::new(),
+        format!("Preflight {}", describe(exchange.preflight, preflight)),
+        format!("Request {}", describe(exchange.actual, actual)),
+    )];
+    cells.extend(rows.into_iter().map(|(name, pre, act)| {
+        let missing = "—".to_string();
+        (name.to_string(), pre.unwrap_or_else(|| missing.clone()), act.unwrap_or(missing))
+    }));
+    let pre_width = cells.iter().map(|(_, pre, _)| pre.chars().count()).max().unwrap_or(0);
+    for (i, (name, pre, act)) in cells.into_iter().enumerate() {
+        let value_style = if i == 0 { Style::default().bold() } else { Style::default() };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:<width$}  ", name, width = name_width), Style::default().fg(Color::Blue)),
+            Span::styled(format!("{:<width$}  ", pre, width = pre_width), value_style),
+            Span::styled(act, value_style),
+        ]));
+    }
+
+    if verdict.allowed {
+        lines.push(Line::from(Span::styled(
+            "✓ The browser would let the page read the response.",
+            Style::default().fg(Color::Green),
+        )));
+    }
+    for reason in &verdict.reasons {
+        lines.push(Line::from(Span::styled(format!("✗ {}", reason), Style::default().fg(Color::LightRed))));
+    }
+    for note in &verdict.notes {
+        lines.push(Line::from(Span::styled(format!("  {}", note), dim)));
+    }
+    lines
+}
+
+// ── ReportPreview ─────────────────────────────────────────────────────────────
+
+/// Capture-wide findings, one section each; rows involving the selected entry
+/// are highlighted.
+pub struct ReportPreview {
+    lines: Vec<Line<'static>>,
+    scroll: u16,
+    active_focus: ActiveFocus,
+    search_regex: Option<regex::Regex>,
+}
+
+impl ReportPreview {
+    pub fn init(app: &App) -> Self {
+        let selected = app.get_entry_index();
+        let mut lines = endpoint_lines(app.endpoints.as_deref().unwrap_or_default(), selected);
+        lines.push(Line::raw(""));
+        lines.extend(duplicate_lines(app.duplicates.as_deref().unwrap_or_default(), selected));
+        lines.push(Line::raw(""));
+        lines.extend(repeated_download_lines(
+            app.repeated_downloads.as_deref().unwrap_or_default(),
+            selected,
+        ));
+        Self {
+            lines,
+            scroll: clamp_scroll(app.scroll),
+            active_focus: app.active_focus,
+            search_regex: app.search_regex.clone(),
+        }
+    }
+}
+
+impl Widget for ReportPreview {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let lines = apply_highlights(self.lines.into_iter(), self.search_regex.as_ref());
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Report")
+                    .border_style(if self.active_focus == ActiveFocus::Preview {
+                        Style::default().fg(Color::Green)
+                    } else {
+                        Style::default().fg(Color::DarkGray)
+                    }),
+            )
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0));
+        Widget::render(paragraph, area, buf);
+    }
+}
+
+fn byte_size(bytes: u64) -> String {
+    byte_unit::Byte::from_u64(bytes)
+        .get_appropriate_unit(byte_unit::UnitType::Decimal)
+        .to_string()
+}
+
+/// One row per endpoint: count, latency percentiles, average size and statuses.
+fn endpoint_lines(endpoints: &[endpoints::Endpoint], selected: usize) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut lines = vec![section_heading(&format!(
+        "Endpoints — {} endpoint{}",
+        endpoints.len(),
+        if endpoints.len() == 1 { "" } else { "s" }
+    ))];
+    if endpoints.is_empty() {
+        return lines;
+    }
+    let method_width = endpoints.iter().map(|e| e.method.len()).max().unwrap_or(0);
+    lines.push(Line::from(Span::styled(
+        format!(
+            "{:>5}  {:>7} {:>7} {:>7} {:>7}  {:>10}  {:<method_width$} Endpoint",
+            "Count",
+            "p50",
+            "p90",
+            "p99",
+            "max",
+            "Avg size",
+            "",
+            method_width = method_width
+        ),
+        Style::default().bold(),
+    )));
+    let ms = |t: f64| format!("{:.0} ms", t);
+    for endpoint in endpoints {
+        let style = if endpoint.entries.contains(&selected) {
+            Style::default().fg(Color::Yellow).bold()
+        } else {
+            Style::default()
+        };
+        let statuses: Vec<Span<'static>> = endpoint
+            .statuses
+            .iter()
+            .map(|(status, count)| {
+                let color = match status {
+                    200..=299 => Color::Green,
+                    300..=399 => Color::Cyan,
+                    400..=499 => Color::Yellow,
+                    _ => Color::LightRed,
+                };
+                Span::styled(format!("  {}×{}", status, count), Style::default().fg(color))
+            })
+            .collect();
+        let mut spans = vec![
+            Span::styled(
+                format!(
+                    "{:>5}  {:>7} {:>7} {:>7} {:>7}  {:>10}  ",
+                    endpoint.entries.len(),
+                    ms(endpoint.p50),
+                    ms(endpoint.p90),
+                    ms(endpoint.p99),
+                    ms(endpoint.max),
+                    byte_size(endpoint.avg_size)
+                ),
+                style,
+            ),
+            Span::styled(
+                format!("{:<method_width$} ", endpoint.method, method_width = method_width),
+                style.fg(Color::Cyan),
+            ),
+            Span::styled(endpoint.template.clone(), style),
+        ];
+        spans.extend(statuses);
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(Span::styled(
+        "Numeric ids, UUIDs and hashes in paths are shown as {id}; query strings are ignored.",
+        dim,
+    )));
+    lines
+}
+
+/// Requests sent more than once, with what the repeats cost.
+fn duplicate_lines(groups: &[duplicates::Group], selected: usize) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(Color::DarkGray);
+    if groups.is_empty() {
+        return vec![
+            section_heading("Duplicate Requests"),
+            Line::from(Span::styled("No request was sent more than once.", dim)),
+        ];
+    }
+    let repeats: usize = groups.iter().map(|g| g.entries.len() - 1).sum();
+    let bytes: u64 = groups.iter().map(|g| g.wasted_bytes).sum();
+    let ms: u64 = groups.iter().map(|g| g.wasted_ms).sum();
+    let mut lines = vec![
+        section_heading(&format!(
+            "Duplicate Requests — {} group{}, {} redundant request{}, {} and {} ms wasted",
+            groups.len(),
+            if groups.len() == 1 { "" } else { "s" },
+            repeats,
+            if repeats == 1 { "" } else { "s" },
+            byte_size(bytes),
+            ms
+        )),
+        Line::from(Span::styled("Press U to show only duplicates in the table.", dim)),
+    ];
+    for group in groups {
+        let style = if group.entries.contains(&selected) {
+            Style::default().fg(Color::Yellow).bold()
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:>3}×  ", group.entries.len()), style),
+            Span::styled(format!("{:>10}  ", byte_size(group.wasted_bytes)), style),
+            Span::styled(format!("{:>7} ms  ", group.wasted_ms), style),
+            Span::styled(format!("{} ", group.method), style.fg(Color::Cyan)),
+            Span::styled(group.url.clone(), style),
+        ]));
+        let numbers: Vec<String> = group.entries.iter().map(|i| format!("#{}", i + 1)).collect();
+        let kind = if group.identical {
+            "identical"
+        } else {
+            "near-identical (query order, cache busters or body formatting differ)"
+        };
+        lines.push(Line::from(Span::styled(format!("      {} — {}", numbers.join(" "), kind), dim)));
+    }
+    lines
+}
+
+/// Assets downloaded again although they did not change.
+fn repeated_download_lines(repeats: &[caching::Repeat], selected: usize) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(Color::DarkGray);
+    if repeats.is_empty() {
+        return vec![
+            section_heading("Repeated Downloads"),
+            Line::from(Span::styled("No asset was downloaded again unchanged.", dim)),
+        ];
+    }
+    let wasted: u64 = repeats.iter().map(|r| r.wasted_bytes).sum();
+    let mut lines = vec![section_heading(&format!(
+        "Repeated Downloads — {} asset{}, {} downloaded again",
+        repeats.len(),
+        if repeats.len() == 1 { "" } else { "s" },
+        byte_size(wasted)
+    ))];
+    for repeat in repeats {
+        let style = if repeat.entries.contains(&selected) {
+            Style::default().fg(Color::Yellow).bold()
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:>3}×  ", repeat.entries.len()), style),
+            Span::styled(format!("{:>10}  ", byte_size(repeat.wasted_bytes)), style),
+            Span::styled(repeat.url.clone(), style),
+        ]));
+        let numbers: Vec<String> = repeat.entries.iter().map(|i| format!("#{}", i + 1)).collect();
+        lines.push(Line::from(Span::styled(
+            format!("      {} — {}", numbers.join(" "), repeat.reason),
+            dim,
+        )));
+    }
+    lines
+}
+
 // ── RequestPreview ────────────────────────────────────────────────────────────
 
 pub struct RequestPreview<'a> {
     app: &'a App,
-    scroll: u16,
+    scroll: usize,
     active_focus: ActiveFocus,
 }
 
@@ -515,35 +1218,15 @@ impl<'a> Widget for RequestPreview<'a> {
     where
         Self: Sized,
     {
-        let text = if let Some(cached) = &self.app.cached_preview_text {
-            let start = self.scroll as usize;
-            let height = area.height as usize;
-            if start >= cached.lines.len() {
-                Text::default()
-            } else {
-                let re_opt = self.app.search_regex.as_ref();
-                let lines: Vec<Line<'static>> = cached
-                    .lines
-                    .iter()
-                    .skip(start)
-                    .take(height)
-                    .map(|line| truncate_line(line, 2000))
-                    .map(|line| match re_opt {
-                        Some(re) => highlight_line_matches(line, re),
-                        None => line,
-                    })
-                    .collect();
-                Text::from(lines)
-            }
-        } else {
-            Text::raw("Loading or No Body...")
-        };
+        let text = visible_body_text(self.app, self.scroll, area.height as usize)
+            .unwrap_or_else(|| Text::raw("Loading or No Body..."));
+        let title = body_title(self.app, "Request Body", self.scroll);
 
         let mut paragraph = Paragraph::new(text)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title("Request Body")
+                    .title(title)
                     .border_style(if self.active_focus == ActiveFocus::Preview {
                         Style::default().fg(Color::Green)
                     } else {
@@ -552,7 +1235,7 @@ impl<'a> Widget for RequestPreview<'a> {
             )
             .scroll((0, 0));
 
-        if self.app.enable_syntax_highlighting {
+        if self.app.enable_syntax_highlighting && self.app.body_view == BodyView::Text {
             paragraph = paragraph.wrap(Wrap { trim: false });
         }
 
@@ -564,7 +1247,7 @@ impl<'a> Widget for RequestPreview<'a> {
 
 pub struct ResponsePreview<'a> {
     app: &'a App,
-    scroll: u16,
+    scroll: usize,
     active_focus: ActiveFocus,
     was_base64_decoded: bool,
 }
@@ -590,35 +1273,27 @@ impl<'a> ResponsePreview<'a> {
 
 impl<'a> Widget for ResponsePreview<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let text = if let Some(cached) = &self.app.cached_preview_text {
-            let start = self.scroll as usize;
-            let height = area.height as usize;
-            if start >= cached.lines.len() {
-                Text::default()
-            } else {
-                let re_opt = self.app.search_regex.as_ref();
-                let lines: Vec<Line<'static>> = cached
-                    .lines
-                    .iter()
-                    .skip(start)
-                    .take(height)
-                    .map(|line| truncate_line(line, 2000))
-                    .map(|line| match re_opt {
-                        Some(re) => highlight_line_matches(line, re),
-                        None => line,
-                    })
-                    .collect();
-                Text::from(lines)
+        if self.app.body_view == BodyView::Image {
+            if let Some(preview) = &self.app.image_preview {
+                render_image_preview(self.app, preview, self.active_focus, area, buf);
+                return;
             }
-        } else {
-            Text::raw("Loading or No Response Body...")
-        };
+        }
 
-        let title = if self.was_base64_decoded {
-            "Response Body (base64 decoded)"
-        } else {
-            "Response Body"
-        };
+        let text = visible_body_text(self.app, self.scroll, area.height as usize)
+            .unwrap_or_else(|| Text::raw("Loading or No Response Body..."));
+
+        let mut base = String::from("Response Body");
+        if self.was_base64_decoded {
+            base.push_str(" (base64 decoded)");
+        }
+        if let Some(summary) = &self.app.content_encoding_summary {
+            base.push_str(&format!(" ({})", summary));
+        }
+        if let Some(charset) = self.app.body_charset {
+            base.push_str(&format!(" [{}]", charset.name()));
+        }
+        let title = body_title(self.app, &base, self.scroll);
 
         let mut paragraph = Paragraph::new(text)
             .block(
@@ -633,13 +1308,308 @@ impl<'a> Widget for ResponsePreview<'a> {
             )
             .scroll((0, 0));
 
-        if self.app.enable_syntax_highlighting {
+        if self.app.enable_syntax_highlighting && self.app.body_view == BodyView::Text {
             paragraph = paragraph.wrap(Wrap { trim: false });
         }
         Widget::render(paragraph, area, buf);
     }
 }
 
+/// Image body: a metadata line followed by the picture, drawn with half-blocks
+/// or reserved for the terminal graphics protocol to paint after the frame.
+fn render_image_preview(
+    app: &App,
+    preview: &ImagePreview,
+    active_focus: ActiveFocus,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Response Body (image)")
+        .border_style(if active_focus == ActiveFocus::Preview {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        });
+    let inner = block.inner(area);
+    Widget::render(block, area, buf);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Fill(1)])
+        .split(inner);
+
+    let size = byte_unit::Byte::from_u64(preview.byte_size as u64)
+        .get_appropriate_unit(byte_unit::UnitType::Decimal);
+    let dim = Style::default().fg(Color::DarkGray);
+    let meta = Line::from(vec![
+        Span::styled(preview.format, Style::default().fg(Color::Magenta).bold()),
+        Span::styled(" · ", dim),
+        Span::styled(
+            format!("{}×{}", preview.width, preview.height),
+            Style::default().fg(Color::LightCyan),
+        ),
+        Span::styled(" · ", dim),
+        Span::styled(format!("{:.1}", size), Style::default().fg(Color::LightCyan)),
+        Span::styled(" · ", dim),
+        Span::styled(format!("{:?}", app.image_protocol).to_lowercase(), dim),
+    ]);
+    Widget::render(Paragraph::new(meta), chunks[0], buf);
+
+    let image_area = chunks[1];
+    if image_area.is_empty() {
+        return;
+    }
+    if app.image_protocol.is_graphics() {
+        let (cols, rows) = preview.fit_cells(image_area.width, image_area.height);
+        app.image_placement
+            .set(Some(Rect::new(image_area.x, image_area.y, cols, rows)));
+    } else {
+        preview.render_halfblocks(image_area, buf);
+    }
+}
+
+// ── DiffPreview ───────────────────────────────────────────────────────────────
+
+/// Side by side comparison: the marked entry (or the baseline counterpart) on
+/// the left, the selected one on the right.
+pub struct DiffPreview<'a> {
+    app: &'a App,
+    scroll: usize,
+    active_focus: ActiveFocus,
+}
+
+impl<'a> DiffPreview<'a> {
+    pub fn init(app: &'a App) -> Self {
+        Self {
+            app,
+            scroll: app.scroll,
+            active_focus: app.active_focus,
+        }
+    }
+}
+
+impl<'a> Widget for DiffPreview<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let selected = self.app.get_entry_index() + 1;
+        if let Some(replay) = self.app.current_replay() {
+            self.render_replay(replay, selected, area, buf);
+            return;
+        }
+        let left = match (self.app.marked_entry, &self.app.baseline) {
+            (Some(marked), _) => Some(format!("#{} (marked)", marked + 1)),
+            (None, Some(baseline)) => Some(format!("baseline {}", baseline.name)),
+            (None, None) => None,
+        };
+        let title = match (left, &self.app.entry_diff) {
+            (Some(left), Some(diff)) => format!(
+                "Diff: {} ↔ #{} (selected) — {} difference{}",
+                left,
+                selected,
+                diff.changes,
+                if diff.changes == 1 { "" } else { "s" }
+            ),
+            _ => "Diff".to_string(),
+        };
+        let block = self.block(title);
+        let inner = block.inner(area);
+        Widget::render(block, area, buf);
+
+        let Some(diff) = &self.app.entry_diff else {
+            if let Some(baseline) = &self.app.baseline {
+                let hint = Paragraph::new(vec![
+                    Line::from(format!("#{} is new in this capture.", selected)),
+                    Line::from(""),
+                    Line::from(Span::styled(
+                        format!("No request to the same endpoint in {}.", baseline.name),
+                        Style::default().fg(Color::DarkGray),
+                    )),
+                ]);
+                Widget::render(hint, inner, buf);
+                return;
+            }
+            let hint = Paragraph::new(vec![
+                Line::from("No entry marked for comparison."),
+                Line::from(""),
+                Line::from(Span::styled(
+                    "Press R to replay the selected request and compare the new response.",
+                    Style::default().fg(Color::DarkGray),
+                )),
+                Line::from(""),
+                Line::from(Span::styled(
+                    "Press m on an entry, select another one, then press D.",
+                    Style::default().fg(Color::DarkGray),
+                )),
+            ]);
+            Widget::render(hint, inner, buf);
+            return;
+        };
+
+        self.render_rows(diff, inner, buf);
+    }
+}
+
+impl<'a> DiffPreview<'a> {
+    fn block(&self, title: String) -> Block<'static> {
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(if self.active_focus == ActiveFocus::Preview {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            })
+    }
+
+    /// Recorded entry on the left, the replayed response on the right.
+    fn render_replay(&self, replay: &Replay, selected: usize, area: Rect, buf: &mut Buffer) {
+        let (title, message) = match (&replay.result, &self.app.entry_diff) {
+            (None, _) => (
+                format!("Replay: #{} → {}", selected, replay.target),
+                "Waiting for the response…".to_string(),
+            ),
+            (Some(Err(error)), _) => (format!("Replay: #{} — failed", selected), error.clone()),
+            (Some(Ok(entry)), Some(diff)) => (
+                format!(
+                    "Replay: #{} (recorded) ↔ {} ({}, {:.0} ms) — {} difference{}",
+                    selected,
+                    replay.target,
+                    entry.response.status,
+                    entry.time,
+                    diff.changes,
+                    if diff.changes == 1 { "" } else { "s" }
+                ),
+                String::new(),
+            ),
+            (Some(Ok(_)), None) => (format!("Replay: #{}", selected), String::new()),
+        };
+        let block = self.block(title);
+        let inner = block.inner(area);
+        Widget::render(block, area, buf);
+        match &self.app.entry_diff {
+            Some(diff) if message.is_empty() => self.render_rows(diff, inner, buf),
+            _ => Widget::render(Paragraph::new(message).wrap(Wrap { trim: false }), inner, buf),
+        }
+    }
+
+    fn render_rows(&self, diff: &EntryDiff, inner: Rect, buf: &mut Buffer) {
+        let half = inner.width.saturating_sub(1) as usize / 2;
+        let dim = Style::default().fg(Color::DarkGray);
+        let lines: Vec<Line<'static>> = diff
+            .rows
+            .iter()
+            .skip(self.scroll)
+            .take(inner.height as usize)
+            .map(|row| match row {
+                DiffRow::Section(name) => {
+                    Line::from(Span::styled(name.to_string(), Style::default().bold().underlined()))
+                }
+                DiffRow::Gap(n) => Line::from(Span::styled(
+                    format!("  ⋯ {} unchanged line{} ⋯", n, if *n == 1 { "" } else { "s" }),
+                    dim,
+                )),
+                DiffRow::Note(note) => Line::from(Span::styled(format!("  {}", note), dim)),
+                DiffRow::Pair(left, right) => {
+                    let mut spans = diff_cell_spans(left.as_ref(), half);
+                    spans.push(Span::styled("│", dim));
+                    spans.extend(diff_cell_spans(right.as_ref(), half));
+                    Line::from(spans)
+                }
+            })
+            .collect();
+        Widget::render(Paragraph::new(lines), inner, buf);
+    }
+}
+
+// ── SecretsPreview ────────────────────────────────────────────────────────────
+
+/// Secret scan findings across the whole capture; those of the selected entry
+/// are highlighted.
+pub struct SecretsPreview<'a> {
+    app: &'a App,
+    scroll: usize,
+    active_focus: ActiveFocus,
+}
+
+impl<'a> SecretsPreview<'a> {
+    pub fn init(app: &'a App) -> Self {
+        Self {
+            app,
+            scroll: app.scroll,
+            active_focus: app.active_focus,
+        }
+    }
+}
+
+impl<'a> Widget for SecretsPreview<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let findings = self.app.secrets.as_deref().unwrap_or_default();
+        let total = self.app.har.log.entries.len();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Secrets — {}", secret_scan::summary(findings, total)))
+            .border_style(if self.active_focus == ActiveFocus::Preview {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default()
+            });
+        let inner = block.inner(area);
+        Widget::render(block, area, buf);
+
+        if findings.is_empty() {
+            let hint = Paragraph::new(vec![
+                Line::from("No secrets found."),
+                Line::from(""),
+                Line::from(Span::styled(
+                    "Checked URLs, headers, cookies and text bodies for keys, tokens, private keys, card numbers and emails.",
+                    Style::default().fg(Color::DarkGray),
+                )),
+            ])
+            .wrap(Wrap { trim: false });
+            Widget::render(hint, inner, buf);
+            return;
+        }
+
+        let selected = self.app.get_entry_index();
+        let lines: Vec<Line<'static>> = secret_scan::format_rows(findings)
+            .into_iter()
+            .zip(findings)
+            .skip(self.scroll)
+            .take(inner.height as usize)
+            .map(|(row, finding)| {
+                let style = if finding.entry == selected {
+                    Style::default().fg(Color::Yellow).bold()
+                } else {
+                    Style::default()
+                };
+                Line::from(Span::styled(row, style))
+            })
+            .collect();
+        Widget::render(Paragraph::new(lines), inner, buf);
+    }
+}
+
+/// One half of a side by side row: change marker and text, padded to `width`.
+fn diff_cell_spans(cell: Option<&DiffCell>, width: usize) -> Vec<Span<'static>> {
+    let Some(cell) = cell else {
+        return vec![Span::raw(" ".repeat(width))];
+    };
+    let (marker, style) = match cell.change {
+        Change::Same => (' ', Style::default()),
+        Change::Removed => ('-', Style::default().fg(Color::LightRed)),
+        Change::Added => ('+', Style::default().fg(Color::LightGreen)),
+        Change::Changed => ('~', Style::default().fg(Color::LightYellow)),
+    };
+    let text: String = format!("{} {}", marker, cell.text)
+        .chars()
+        .map(|c| if c == '\t' { ' ' } else { c })
+        .take(width)
+        .collect();
+    let pad = width.saturating_sub(text.chars().count());
+    vec![Span::styled(text, style), Span::raw(" ".repeat(pad))]
+}
+
 // ── HelpPreview ───────────────────────────────────────────────────────────────
 
 pub struct HelpPreview {
@@ -650,7 +1620,7 @@ pub struct HelpPreview {
 impl HelpPreview {
     pub fn init(app: &App) -> Self {
         Self {
-            scroll: app.scroll,
+            scroll: clamp_scroll(app.scroll),
             active_focus: app.active_focus,
         }
     }
@@ -706,6 +1676,10 @@ impl Widget for HelpPreview {
                 Span::styled("  Esc           ", key_style),
                 Span::raw("Cancel search (restore) / clear active filter"),
             ]),
+            Line::from(vec![
+                Span::styled("  json:EXPR     ", key_style),
+                Span::raw("In ReqBody/RespBody scope: jq/JSONPath predicate (json:.error != null)"),
+            ]),
             Line::from(""),
             Line::from(Span::styled("Details Pane Scrolling", bold_underline)),
             Line::from(vec![
@@ -727,8 +1701,8 @@ impl Widget for HelpPreview {
             Line::from(""),
             Line::from(Span::styled("Tabs", bold_underline)),
             Line::from(vec![
-                Span::styled("  1-4           ", key_style),
-                Span::raw("Switch to tab (Headers, Cookies, Request, Response)"),
+                Span::styled("  1-6           ", key_style),
+                Span::raw("Switch to tab (Headers, Cookies, Request, Response, Inspect, Report)"),
             ]),
             Line::from(vec![
                 Span::styled("  Left / Right  ", key_style),
@@ -744,6 +1718,78 @@ impl Widget for HelpPreview {
                 Span::styled("  h             ", key_style),
                 Span::raw("Toggle syntax highlighting"),
             ]),
+            Line::from(vec![
+                Span::styled("  x             ", key_style),
+                Span::raw("Toggle hex view of the body (text or image otherwise)"),
+            ]),
+            Line::from(vec![
+                Span::styled("  :             ", key_style),
+                Span::raw("Go to byte offset in hex view (0x1f0 or 496)"),
+            ]),
+            Line::from(vec![
+                Span::styled("  f             ", key_style),
+                Span::raw("Find in body (regex), independent of the table filter"),
+            ]),
+            Line::from(vec![
+                Span::styled("  n / N         ", key_style),
+                Span::raw("Jump to next / previous match in the body"),
+            ]),
+            Line::from(vec![
+                Span::styled("  |             ", key_style),
+                Span::raw("Project JSON body with jq (.items[] | .id) or JSONPath ($..id)"),
+            ]),
+            Line::from(vec![
+                Span::styled("  t             ", key_style),
+                Span::raw("Toggle collapsible JSON tree of the body"),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled("JSON Tree (while open)", bold_underline)),
+            Line::from(vec![
+                Span::styled("  j / k         ", key_style),
+                Span::raw("Move cursor down / up"),
+            ]),
+            Line::from(vec![
+                Span::styled("  Enter / Space ", key_style),
+                Span::raw("Expand or collapse node"),
+            ]),
+            Line::from(vec![
+                Span::styled("  l / h         ", key_style),
+                Span::raw("Expand node / collapse or go to parent"),
+            ]),
+            Line::from(vec![
+                Span::styled("  0-9 / E       ", key_style),
+                Span::raw("Expand all to depth / expand everything"),
+            ]),
+            Line::from(vec![
+                Span::styled("  f / n         ", key_style),
+                Span::raw("Jump to key / next matching key"),
+            ]),
+            Line::from(vec![
+                Span::styled("  y             ", key_style),
+                Span::raw("Copy subtree as JSON (OSC 52 clipboard)"),
+            ]),
+            Line::from(vec![
+                Span::styled("  t / Esc       ", key_style),
+                Span::raw("Close the tree"),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled("Compare Entries", bold_underline)),
+            Line::from(vec![
+                Span::styled("  m             ", key_style),
+                Span::raw("Mark / unmark the selected entry for comparison"),
+            ]),
+            Line::from(vec![
+                Span::styled("  D             ", key_style),
+                Span::raw("Diff the marked entry against the selected one"),
+            ]),
+            Line::from(vec![
+                Span::styled("  R             ", key_style),
+                Span::raw("Replay the selected request to a base URL and diff the response"),
+            ]),
+            Line::from(vec![
+                Span::styled("  E             ", key_style),
+                Span::raw("Edit the request in $EDITOR, then replay it"),
+            ]),
             Line::from(""),
             Line::from(Span::styled(
                 "External Viewers (Request/Response tabs)",
@@ -777,6 +1823,18 @@ impl Widget for HelpPreview {
             ]),
             Line::from(""),
             Line::from(Span::styled("General", bold_underline)),
+            Line::from(vec![
+                Span::styled("  !             ", key_style),
+                Span::raw("Scan the capture for secrets (keys, tokens, card numbers, emails)"),
+            ]),
+            Line::from(vec![
+                Span::styled("  U             ", key_style),
+                Span::raw("Show only duplicate requests in the table (Esc clears)"),
+            ]),
+            Line::from(vec![
+                Span::styled("  S             ", key_style),
+                Span::raw("Export a sanitized copy (credentials and PII masked)"),
+            ]),
             Line::from(vec![
                 Span::styled("  q / Ctrl+C    ", key_style),
                 Span::raw("Quit"),
@@ -786,6 +1844,14 @@ impl Widget for HelpPreview {
                 "  Base64-encoded responses are automatically decoded.",
                 dim,
             )),
+            Line::from(Span::styled(
+                "  Binary bodies (fonts, protobuf, wasm…) open in hex view.",
+                dim,
+            )),
+            Line::from(Span::styled(
+                "  PNG, JPEG, GIF, WebP and SVG responses show an inline image preview.",
+                dim,
+            )),
         ];
 
         let paragraph = Paragraph::new(lines)
@@ -808,8 +1874,169 @@ impl Widget for HelpPreview {
 
 // ── Helpers ───────────────────────────────────────────────────────────────────
 
+/// Convert the app scroll position for widgets that scroll a `Paragraph`.
+fn clamp_scroll(scroll: usize) -> u16 {
+    u16::try_from(scroll).unwrap_or(u16::MAX)
+}
+
+/// Window of body lines for the Request/Response panes: rows of the hex dump or
+/// JSON tree when one of those views is active, otherwise lines of the cached
+/// preview text.
+fn visible_body_text(app: &App, start: usize, height: usize) -> Option<Text<'static>> {
+    let re_opt = app.search_regex.as_ref();
+    let highlight = |line: Line<'static>| match re_opt {
+        Some(re) => highlight_line_matches(line, re),
+        None => line,
+    };
+
+    if app.body_view == BodyView::Hex {
+        let bytes = app.cached_body_bytes.as_ref()?;
+        let lines: Vec<Line<'static>> = hexdump::render_rows(bytes, start, height)
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| highlight_preview_matches(line, app, start + i))
+            .map(highlight)
+            .collect();
+        return Some(Text::from(lines));
+    }
+
+    if app.body_view == BodyView::Tree {
+        let tree = app.json_tree.as_ref()?;
+        // The pane border takes two of the rows.
+        let lines: Vec<Line<'static>> = tree
+            .render_lines(start, height.saturating_sub(2))
+            .into_iter()
+            .map(highlight)
+            .collect();
+        return Some(Text::from(lines));
+    }
+
+    let cached = app.cached_preview_text.as_ref()?;
+    if start >= cached.lines.len() {
+        return Some(Text::default());
+    }
+    let lines: Vec<Line<'static>> = cached
+        .lines
+        .iter()
+        .skip(start)
+        .take(height)
+        .map(|line| truncate_line(line, 2000))
+        .enumerate()
+        .map(|(i, line)| highlight_preview_matches(line, app, start + i))
+        .map(highlight)
+        .collect();
+    Some(Text::from(lines))
+}
+
+/// Mark the in-preview search matches on line `index` of the body, with the
+/// current match in a stronger colour than the others.
+fn highlight_preview_matches(line: Line<'static>, app: &App, index: usize) -> Line<'static> {
+    let matches = &app.preview_matches;
+    let first = matches.partition_point(|m| m.line < index);
+    let on_line = matches[first..].iter().take_while(|m| m.line == index);
+    let ranges: Vec<(usize, usize, Style)> = on_line
+        .enumerate()
+        .map(|(k, m)| {
+            let style = if app.preview_match_index == Some(first + k) {
+                Style::default().bg(Color::LightRed).fg(Color::Black).bold()
+            } else {
+                Style::default().bg(Color::Cyan).fg(Color::Black)
+            };
+            (m.start, m.end, style)
+        })
+        .collect();
+    if ranges.is_empty() {
+        return line;
+    }
+
+    let base_style = line.style;
+    let mut new_spans: Vec<Span<'static>> = Vec::new();
+    let mut pos = 0;
+    for span in line.spans {
+        let text = span.content.into_owned();
+        let span_end = pos + text.len();
+        let mut last = 0;
+        for &(start, end, style) in &ranges {
+            let (s, e) = (start.max(pos) - pos, end.min(span_end).saturating_sub(pos));
+            if s >= e || !text.is_char_boundary(s) || !text.is_char_boundary(e) {
+                continue;
+            }
+            if s > last {
+                new_spans.push(Span::styled(text[last..s].to_string(), span.style));
+            }
+            new_spans.push(Span::styled(text[s..e].to_string(), span.style.patch(style)));
+            last = e;
+        }
+        if last < text.len() {
+            new_spans.push(Span::styled(text[last..].to_string(), span.style));
+        }
+        pos = span_end;
+    }
+    Line::from(new_spans).style(base_style)
+}
+
+/// Pane title: the view state from `body_title_state`, followed by the
+/// in-preview search position ("match 3/17") or its prompt.
+fn body_title(app: &App, base: &str, scroll: usize) -> String {
+    if let Some(input) = &app.preview_search_input {
+        let error = if app.preview_search_error { " (invalid regex)" } else { "" };
+        return format!("{} — Find: {}▏{}", base, input, error);
+    }
+    let title = body_title_state(app, base, scroll);
+    if app.preview_search.is_none() {
+        return title;
+    }
+    match (app.preview_match_index, app.preview_matches.len()) {
+        (_, 0) => format!("{} — no matches", title),
+        (Some(i), n) => format!("{} — match {}/{}", title, i + 1, n),
+        (None, n) => format!("{} — {} matches", title, n),
+    }
+}
+
+/// Base title extended with the current offset in hex view, the cursor path in
+/// the JSON tree, the body query and its outcome, or the active prompt.
+fn body_title_state(app: &App, base: &str, scroll: usize) -> String {
+    if let Some(input) = &app.offset_input {
+        return format!("{} — Goto offset: {}▏", base, input);
+    }
+    if let (Some(tree), BodyView::Tree) = (&app.json_tree, app.body_view) {
+        if let Some(input) = &tree.find_input {
+            return format!("{} — Find key: {}▏", base, input);
+        }
+        let path = json_tree::format_path(tree.cursor_path());
+        return match &tree.message {
+            Some(message) => format!("{} [tree {}] — {}", base, path, message),
+            None => format!("{} [tree {}]", base, path),
+        };
+    }
+    if app.body_query_editing || (app.body_view == BodyView::Text && !app.body_query.is_empty()) {
+        let status = match &app.body_query_status {
+            _ if app.body_query.trim().is_empty() => String::new(),
+            Some(Ok((n, true))) => format!(" (stopped after {} results)", n),
+            Some(Ok((1, false))) => " (1 result)".to_string(),
+            Some(Ok((n, false))) => format!(" ({} results)", n),
+            Some(Err(e)) => format!(" ({})", e),
+            None => " (body is not JSON)".to_string(),
+        };
+        return if app.body_query_editing {
+            format!("{} — Query: {}▏{}", base, app.body_query, status)
+        } else {
+            format!("{} [query {}]{}", base, app.body_query, status)
+        };
+    }
+    match (&app.cached_body_bytes, app.body_view) {
+        (Some(bytes), BodyView::Hex) => format!(
+            "{} [hex 0x{:08x} / 0x{:08x}]",
+            base,
+            (scroll * hexdump::BYTES_PER_ROW).min(bytes.len()),
+            bytes.len()
+        ),
+        _ => base.to_string(),
+    }
+}
+
 /// Build a table row for `item`, highlighting any regex matches in each cell.
-fn make_row(item: &TableItem, re: Option<&regex::Regex>) -> Row<'static> {
+fn make_row(item: &TableItem, re: Option<&regex::Regex>, show_source: bool) -> Row<'static> {
     let status_style = match item.status {
         100..=199 => Style::default().fg(Color::LightBlue),
         200..=299 => Style::default().fg(Color::LightGreen),
@@ -818,14 +2045,15 @@ fn make_row(item: &TableItem, re: Option<&regex::Regex>) -> Row<'static> {
         500..=599 => Style::default().fg(Color::LightMagenta),
         _ => Style::default().fg(Color::DarkGray),
     };
-    Row::new(vec![
+    let source = show_source.then(|| Cell::from(item.source.clone()).style(Style::default().fg(Color::Gray)));
+    Row::new(source.into_iter().chain([
         hl_cell(&item.status.to_string(), status_style, re),
         hl_cell(&item.method, Style::default().fg(Color::Yellow), re),
         hl_cell(&item.url, Style::default().fg(Color::LightBlue), re),
         hl_cell(&item.mime_type, Style::default().fg(Color::Magenta), re),
         hl_cell(&item.total_size, Style::default().fg(Color::LightCyan), re),
         hl_cell(&item.timestamp, Style::default(), re),
-    ])
+    ]))
 }
 
 /// Build a single `Cell` whose text has regex matches highlighted.